@@ -6,6 +6,7 @@ use header;
 use method;
 use status;
 use uri;
+use version;
 
 /// A generic "error" for HTTP connections
 ///
@@ -32,11 +33,96 @@ enum ErrorKind {
     HeaderNameShared(header::InvalidHeaderNameBytes),
     HeaderValue(header::InvalidHeaderValue),
     HeaderValueShared(header::InvalidHeaderValueBytes),
+    TooManyValues(header::TooManyValues),
+    MaxSizeReached(header::MaxSizeReached),
+    MissingHeaders(header::MissingHeaders),
+    Version(version::InvalidVersion),
+    Status(status::StatusCode),
+    Multiple(Vec<Error>),
+}
+
+impl Error {
+    /// Returns the status code this error was created from, if any.
+    ///
+    /// This allows handlers to bail out early with `?` and still recover the
+    /// intended response status from the resulting `Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{Error, StatusCode};
+    /// let err: Error = StatusCode::BAD_REQUEST.into();
+    /// assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+    ///
+    /// let err: Error = StatusCode::from_u16(1000).unwrap_err().into();
+    /// assert_eq!(err.status(), None);
+    /// ```
+    pub fn status(&self) -> Option<status::StatusCode> {
+        match self.inner {
+            ErrorKind::Status(status) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Returns the individual errors, if this error combines several.
+    ///
+    /// The request and response builders return such an error from `body`
+    /// when more than one argument was invalid. Its description is that of
+    /// the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let err = Request::builder()
+    ///     .header("Bad Name", "a")
+    ///     .header("x-bad-value", "b\r\n")
+    ///     .body(())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.errors().unwrap().len(), 2);
+    ///
+    /// let err = Request::builder()
+    ///     .header("Bad Name", "a")
+    ///     .body(())
+    ///     .unwrap_err();
+    ///
+    /// assert!(err.errors().is_none());
+    /// ```
+    pub fn errors(&self) -> Option<&[Error]> {
+        match self.inner {
+            ErrorKind::Multiple(ref errors) => Some(errors),
+            _ => None,
+        }
+    }
+
+    /// Combines `errors`, which must not be empty, into one error.
+    pub(crate) fn combine(mut errors: Vec<Error>) -> Error {
+        debug_assert!(!errors.is_empty());
+
+        if errors.len() == 1 {
+            errors.pop().expect("errors is not empty")
+        } else {
+            Error { inner: ErrorKind::Multiple(errors) }
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        error::Error::description(self).fmt(f)
+        match self.inner {
+            ErrorKind::Status(ref status) => status.fmt(f),
+            ErrorKind::Multiple(ref errors) => {
+                for (i, e) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    e.fmt(f)?;
+                }
+                Ok(())
+            }
+            _ => error::Error::description(self).fmt(f),
+        }
     }
 }
 
@@ -54,6 +140,12 @@ impl error::Error for Error {
             HeaderNameShared(ref e) => e.description(),
             HeaderValue(ref e) => e.description(),
             HeaderValueShared(ref e) => e.description(),
+            TooManyValues(ref e) => e.description(),
+            MaxSizeReached(ref e) => e.description(),
+            MissingHeaders(ref e) => e.description(),
+            Version(ref e) => e.description(),
+            Status(ref s) => s.canonical_reason().unwrap_or("unknown status code"),
+            Multiple(ref errors) => errors[0].description(),
         }
     }
 }
@@ -112,6 +204,36 @@ impl From<header::InvalidHeaderValueBytes> for Error {
     }
 }
 
+impl From<header::TooManyValues> for Error {
+    fn from(err: header::TooManyValues) -> Error {
+        Error { inner: ErrorKind::TooManyValues(err) }
+    }
+}
+
+impl From<header::MaxSizeReached> for Error {
+    fn from(err: header::MaxSizeReached) -> Error {
+        Error { inner: ErrorKind::MaxSizeReached(err) }
+    }
+}
+
+impl From<header::MissingHeaders> for Error {
+    fn from(err: header::MissingHeaders) -> Error {
+        Error { inner: ErrorKind::MissingHeaders(err) }
+    }
+}
+
+impl From<version::InvalidVersion> for Error {
+    fn from(err: version::InvalidVersion) -> Error {
+        Error { inner: ErrorKind::Version(err) }
+    }
+}
+
+impl From<status::StatusCode> for Error {
+    fn from(status: status::StatusCode) -> Error {
+        Error { inner: ErrorKind::Status(status) }
+    }
+}
+
 // A crate-private type until we can use !.
 //
 // Being crate-private, we should be able to swap the type out in a
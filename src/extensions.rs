@@ -119,6 +119,54 @@ impl Extensions {
             .and_then(|boxed| (&mut **boxed as &mut (Any + 'static)).downcast_mut())
     }
 
+    /// Get a mutable reference to a type, inserting the value returned by
+    /// `f` if it is not present yet.
+    ///
+    /// `f` is only called when no extension of this type exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// *ext.get_or_insert_with(|| 0u32) += 1;
+    /// *ext.get_or_insert_with(|| 0u32) += 1;
+    ///
+    /// assert_eq!(ext.get::<u32>(), Some(&2));
+    /// ```
+    pub fn get_or_insert_with<T, F>(&mut self, f: F) -> &mut T
+        where T: Send + Sync + 'static,
+              F: FnOnce() -> T,
+    {
+        let boxed = self
+            .map
+            .get_or_insert_with(|| Box::new(HashMap::default()))
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()));
+
+        boxed
+            .downcast_mut()
+            .expect("extension is stored under its own type id")
+    }
+
+    /// Get a mutable reference to a type, inserting `value` if it is not
+    /// present yet.
+    ///
+    /// If an extension of this type already exists, `value` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// ext.get_or_insert(String::from("Hello")).push_str(" World");
+    ///
+    /// assert_eq!(ext.get_or_insert(String::new()), "Hello World");
+    /// ```
+    pub fn get_or_insert<T: Send + Sync + 'static>(&mut self, value: T) -> &mut T {
+        self.get_or_insert_with(|| value)
+    }
+
 
     /// Remove a type from this `Extensions`.
     ///
@@ -147,8 +195,77 @@ impl Extensions {
             })
     }
 
+    /// Move all extensions from `other` into this `Extensions`.
+    ///
+    /// If both contain an extension of the same type, the one from `other`
+    /// replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut parent = Extensions::new();
+    /// parent.insert(1i32);
+    ///
+    /// let mut child = Extensions::new();
+    /// child.insert(2i32);
+    /// child.insert("child");
+    ///
+    /// parent.extend(child);
+    ///
+    /// assert_eq!(parent.get::<i32>(), Some(&2));
+    /// assert_eq!(parent.get::<&'static str>(), Some(&"child"));
+    /// ```
+    pub fn extend(&mut self, other: Extensions) {
+        let other = match other.map {
+            Some(other) => other,
+            None => return,
+        };
+
+        match self.map {
+            Some(ref mut map) => map.extend(*other),
+            None => self.map = Some(other),
+        }
+    }
+
+    /// Returns the number of extensions stored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// assert_eq!(ext.len(), 0);
+    ///
+    /// ext.insert(5i32);
+    /// assert_eq!(ext.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.as_ref().map_or(0, |map| map.len())
+    }
+
+    /// Returns true if no extension is stored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// assert!(ext.is_empty());
+    ///
+    /// ext.insert(5i32);
+    /// assert!(!ext.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Clear the `Extensions` of all inserted extensions.
     ///
+    /// The backing storage is kept, to be reused by later insertions.
+    ///
     /// # Example
     ///
     /// ```
@@ -193,3 +310,72 @@ fn test_extensions() {
     assert_eq!(extensions.get::<bool>(), None);
     assert_eq!(extensions.get(), Some(&MyType(10)));
 }
+
+#[test]
+fn test_get_or_insert_with() {
+    let mut extensions = Extensions::new();
+    let mut calls = 0;
+
+    {
+        let value = extensions.get_or_insert_with(|| { calls += 1; vec![1i32] });
+        value.push(2);
+    }
+
+    extensions.get_or_insert_with(|| { calls += 1; Vec::<i32>::new() }).push(3);
+
+    assert_eq!(calls, 1);
+    assert_eq!(extensions.get::<Vec<i32>>(), Some(&vec![1, 2, 3]));
+
+    assert_eq!(*extensions.get_or_insert(5u8), 5);
+    *extensions.get_or_insert(7u8) += 1;
+    assert_eq!(extensions.get::<u8>(), Some(&6));
+}
+
+#[test]
+fn test_len_and_clear() {
+    let mut extensions = Extensions::new();
+    assert_eq!(extensions.len(), 0);
+    assert!(extensions.is_empty());
+
+    extensions.insert(5i32);
+    extensions.insert("hello");
+    extensions.insert(6i32);
+    assert_eq!(extensions.len(), 2);
+    assert!(!extensions.is_empty());
+
+    let capacity = extensions.map.as_ref().unwrap().capacity();
+    extensions.clear();
+
+    assert!(extensions.is_empty());
+    assert_eq!(extensions.get::<i32>(), None);
+    assert_eq!(extensions.get::<&'static str>(), None);
+    assert_eq!(extensions.map.as_ref().unwrap().capacity(), capacity);
+}
+
+#[test]
+fn test_extend() {
+    #[derive(Debug, PartialEq)]
+    struct MyType(i32);
+
+    let mut parent = Extensions::new();
+    parent.insert(5i32);
+    parent.insert(MyType(1));
+
+    let mut child = Extensions::new();
+    child.insert(6i32);
+    child.insert(true);
+
+    parent.extend(child);
+
+    assert_eq!(parent.len(), 3);
+    assert_eq!(parent.get::<i32>(), Some(&6));
+    assert_eq!(parent.get::<bool>(), Some(&true));
+    assert_eq!(parent.get::<MyType>(), Some(&MyType(1)));
+
+    let mut empty = Extensions::new();
+    empty.extend(parent);
+    assert_eq!(empty.len(), 3);
+
+    empty.extend(Extensions::new());
+    assert_eq!(empty.len(), 3);
+}
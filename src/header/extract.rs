@@ -0,0 +1,175 @@
+//! Building typed structs out of a `HeaderMap`.
+
+use std::error::Error;
+use std::fmt;
+
+use super::{HeaderMap, HeaderName, HeaderValue};
+
+/// Types that can be extracted from a `HeaderMap`.
+///
+/// This is the foundation for typed access to a set of headers. It is
+/// usually implemented with the `require` and `optional` helpers, which look
+/// up single-valued headers and report problems as a `HeaderError`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderMap;
+/// # use http::header::{self, FromHeaders, HeaderError, HOST};
+/// #[derive(Debug)]
+/// struct Host(String);
+///
+/// impl FromHeaders for Host {
+///     fn from_headers(map: &HeaderMap) -> Result<Host, HeaderError> {
+///         let value = header::require(map, HOST)?;
+///         let host = value.to_str().map_err(|_| HeaderError::invalid(HOST))?;
+///         Ok(Host(host.to_string()))
+///     }
+/// }
+///
+/// let mut map = HeaderMap::new();
+/// assert!(Host::from_headers(&map).unwrap_err().is_missing());
+///
+/// map.insert(HOST, "example.com".parse().unwrap());
+/// assert_eq!(Host::from_headers(&map).unwrap().0, "example.com");
+/// ```
+pub trait FromHeaders: Sized {
+    /// Extracts `Self` from the headers in `map`.
+    fn from_headers(map: &HeaderMap) -> Result<Self, HeaderError>;
+}
+
+/// An error extracting a typed value from a `HeaderMap`.
+///
+/// The error records the name of the offending header.
+#[derive(Debug)]
+pub struct HeaderError {
+    name: HeaderName,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Missing,
+    Duplicate,
+    Invalid,
+}
+
+/// Returns the single value of the header `name`.
+///
+/// An error is returned if the header is absent or has more than one value.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderMap;
+/// # use http::header::{self, CONTENT_TYPE};
+/// let mut map = HeaderMap::new();
+/// assert!(header::require(&map, CONTENT_TYPE).is_err());
+///
+/// map.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+/// assert_eq!(header::require(&map, CONTENT_TYPE).unwrap(), "text/plain");
+///
+/// map.append(CONTENT_TYPE, "text/html".parse().unwrap());
+/// assert!(header::require(&map, CONTENT_TYPE).unwrap_err().is_duplicate());
+/// ```
+pub fn require<'a>(map: &'a HeaderMap, name: HeaderName)
+    -> Result<&'a HeaderValue, HeaderError>
+{
+    match optional(map, name.clone())? {
+        Some(value) => Ok(value),
+        None => Err(HeaderError::new(name, Kind::Missing)),
+    }
+}
+
+/// Returns the value of the header `name`, if present.
+///
+/// An error is returned if the header has more than one value.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderMap;
+/// # use http::header::{self, CONTENT_LENGTH};
+/// let mut map = HeaderMap::new();
+/// assert!(header::optional(&map, CONTENT_LENGTH).unwrap().is_none());
+///
+/// map.insert(CONTENT_LENGTH, "42".parse().unwrap());
+/// assert_eq!(header::optional(&map, CONTENT_LENGTH).unwrap().unwrap(), "42");
+/// ```
+pub fn optional<'a>(map: &'a HeaderMap, name: HeaderName)
+    -> Result<Option<&'a HeaderValue>, HeaderError>
+{
+    let mut values = map.get_all(&name).iter();
+
+    let value = match values.next() {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    if values.next().is_some() {
+        return Err(HeaderError::new(name, Kind::Duplicate));
+    }
+
+    Ok(Some(value))
+}
+
+// ===== impl HeaderError =====
+
+impl HeaderError {
+    /// Creates an error reporting that the value of the header `name` could
+    /// not be decoded.
+    ///
+    /// This is intended for use by `FromHeaders` implementations.
+    pub fn invalid(name: HeaderName) -> HeaderError {
+        HeaderError::new(name, Kind::Invalid)
+    }
+
+    fn new(name: HeaderName, kind: Kind) -> HeaderError {
+        HeaderError {
+            name: name,
+            kind: kind,
+        }
+    }
+
+    /// Returns the name of the header that caused the error.
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+
+    /// Returns true if the error was caused by a missing header.
+    pub fn is_missing(&self) -> bool {
+        self.kind == Kind::Missing
+    }
+
+    /// Returns true if the error was caused by a header having more than one
+    /// value.
+    pub fn is_duplicate(&self) -> bool {
+        self.kind == Kind::Duplicate
+    }
+
+    /// Returns true if the error was caused by a header value that could not
+    /// be decoded.
+    pub fn is_invalid(&self) -> bool {
+        self.kind == Kind::Invalid
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self.kind {
+            Kind::Missing => "missing header",
+            Kind::Duplicate => "duplicate header",
+            Kind::Invalid => "invalid header value",
+        }
+    }
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.as_str(), self.name)
+    }
+}
+
+impl Error for HeaderError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+}
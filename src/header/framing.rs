@@ -0,0 +1,162 @@
+//! Helpers for the header fields that determine message framing.
+
+use std::error::Error;
+use std::fmt;
+
+use super::{HeaderMap, CONTENT_LENGTH, TRANSFER_ENCODING};
+use super::list;
+
+/// An error returned when the `Content-Length` of a message is invalid.
+///
+/// This is the case when a value is not a non-negative decimal integer, or
+/// when multiple values disagree. Such a message cannot be reliably framed
+/// and should be rejected.
+#[derive(Debug)]
+pub struct InvalidContentLength {
+    _priv: (),
+}
+
+/// Parses the `Content-Length` header fields of `headers`, as described by
+/// RFC 7230, Section 3.3.2.
+///
+/// Repeated fields, or a single field containing a comma-separated list, are
+/// accepted only when every value is identical.
+pub(crate) fn content_length(headers: &HeaderMap)
+    -> Result<Option<u64>, InvalidContentLength>
+{
+    let mut length = None;
+
+    for value in headers.get_all(CONTENT_LENGTH) {
+        for element in value.as_bytes().split(|&b| b == b',') {
+            let n = parse_length(list::trim_ows(element))?;
+
+            match length {
+                Some(prev) if prev != n => return Err(InvalidContentLength::new()),
+                _ => length = Some(n),
+            }
+        }
+    }
+
+    Ok(length)
+}
+
+/// Returns true if the final transfer coding of the `Transfer-Encoding`
+/// header fields of `headers` is `chunked`, as described by RFC 7230,
+/// Section 3.3.3.
+pub(crate) fn is_chunked(headers: &HeaderMap) -> bool {
+    headers.get_all(TRANSFER_ENCODING)
+        .iter()
+        .flat_map(|value| value.split_commas())
+        .last()
+        .map(|coding| coding.eq_ignore_ascii_case(b"chunked"))
+        .unwrap_or(false)
+}
+
+/// Parses a single `Content-Length` value, which must be `1*DIGIT`.
+pub(super) fn parse_length(digits: &[u8]) -> Result<u64, InvalidContentLength> {
+    if digits.is_empty() {
+        return Err(InvalidContentLength::new());
+    }
+
+    let mut n: u64 = 0;
+
+    for &b in digits {
+        if b < b'0' || b > b'9' {
+            return Err(InvalidContentLength::new());
+        }
+
+        n = n.checked_mul(10)
+            .and_then(|n| n.checked_add((b - b'0') as u64))
+            .ok_or_else(InvalidContentLength::new)?;
+    }
+
+    Ok(n)
+}
+
+// ===== impl InvalidContentLength =====
+
+impl InvalidContentLength {
+    fn new() -> InvalidContentLength {
+        InvalidContentLength { _priv: () }
+    }
+}
+
+impl fmt::Display for InvalidContentLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid content-length")
+    }
+}
+
+impl Error for InvalidContentLength {
+    fn description(&self) -> &str {
+        "invalid content-length"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(values: &[&'static str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &value in values {
+            headers.append(CONTENT_LENGTH, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_content_length() {
+        assert_eq!(content_length(&headers(&[])).unwrap(), None);
+        assert_eq!(content_length(&headers(&["0"])).unwrap(), Some(0));
+        assert_eq!(content_length(&headers(&["42"])).unwrap(), Some(42));
+        assert_eq!(content_length(&headers(&["42", "42"])).unwrap(), Some(42));
+        assert_eq!(content_length(&headers(&["42, 42"])).unwrap(), Some(42));
+        assert_eq!(content_length(&headers(&["18446744073709551615"])).unwrap(),
+                   Some(::std::u64::MAX));
+    }
+
+    #[test]
+    fn test_is_chunked() {
+        fn chunked(values: &[&'static str]) -> bool {
+            let mut headers = HeaderMap::new();
+            for &value in values {
+                headers.append(TRANSFER_ENCODING, value.parse().unwrap());
+            }
+            is_chunked(&headers)
+        }
+
+        assert!(chunked(&["chunked"]));
+        assert!(chunked(&["Chunked"]));
+        assert!(chunked(&["gzip, chunked"]));
+        assert!(chunked(&["gzip", "chunked"]));
+        assert!(chunked(&["gzip,chunked ,"]));
+
+        assert!(!chunked(&[]));
+        assert!(!chunked(&[""]));
+        assert!(!chunked(&["gzip"]));
+        assert!(!chunked(&["chunked, gzip"]));
+        assert!(!chunked(&["chunked", "gzip"]));
+        assert!(!chunked(&["xchunked"]));
+    }
+
+    #[test]
+    fn test_invalid_content_length() {
+        let invalid: &[&[&'static str]] = &[
+            &[""],
+            &["-1"],
+            &["+1"],
+            &["0x10"],
+            &["4 2"],
+            &["42abc"],
+            &["42,"],
+            &["42", "43"],
+            &["42, 43"],
+            &["18446744073709551616"],
+        ];
+
+        for values in invalid {
+            assert!(content_length(&headers(values)).is_err(), "{:?}", values);
+        }
+    }
+}
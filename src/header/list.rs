@@ -0,0 +1,142 @@
+//! Helpers for parsing list-based header field values.
+
+use std::borrow::Cow;
+
+/// An iterator splitting a byte slice on a delimiter, ignoring delimiters
+/// that appear within quoted-strings.
+///
+/// Segments are yielded untrimmed, including empty ones.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitQuoted<'a> {
+    rem: &'a [u8],
+    delim: u8,
+    done: bool,
+}
+
+impl<'a> SplitQuoted<'a> {
+    pub(crate) fn new(src: &'a [u8], delim: u8) -> SplitQuoted<'a> {
+        SplitQuoted {
+            rem: src,
+            delim: delim,
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for SplitQuoted<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.done {
+            return None;
+        }
+
+        let mut quoted = false;
+        let mut escaped = false;
+
+        for (i, &b) in self.rem.iter().enumerate() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+
+            if quoted && b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                quoted = !quoted;
+            } else if b == self.delim && !quoted {
+                let segment = &self.rem[..i];
+                self.rem = &self.rem[i + 1..];
+                return Some(segment);
+            }
+        }
+
+        self.done = true;
+        Some(self.rem)
+    }
+}
+
+/// Strips leading and trailing optional whitespace (`SP` and `HTAB`).
+pub(crate) fn trim_ows(mut s: &[u8]) -> &[u8] {
+    while let Some((&b, rest)) = s.split_first() {
+        if !is_ows(b) {
+            break;
+        }
+        s = rest;
+    }
+
+    while let Some((&b, rest)) = s.split_last() {
+        if !is_ows(b) {
+            break;
+        }
+        s = rest;
+    }
+
+    s
+}
+
+/// Removes the surrounding DQUOTEs of a quoted-string and resolves its
+/// quoted-pairs. Values that are not quoted are returned unchanged.
+pub(crate) fn unquote<'a>(s: &'a str) -> Cow<'a, str> {
+    if s.len() < 2 || !s.starts_with('"') || !s.ends_with('"') {
+        return Cow::Borrowed(s);
+    }
+
+    let inner = &s[1..s.len() - 1];
+
+    if !inner.contains('\\') {
+        return Cow::Borrowed(inner);
+    }
+
+    let mut dst = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(c) = chars.next() {
+                dst.push(c);
+            }
+        } else {
+            dst.push(c);
+        }
+    }
+
+    Cow::Owned(dst)
+}
+
+#[inline]
+fn is_ows(b: u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_quoted() {
+        let parts: Vec<_> = SplitQuoted::new(b"a, \"b,c\" ,,d", b',').collect();
+        assert_eq!(parts, [&b"a"[..], b" \"b,c\" ", b"", b"d"]);
+
+        let parts: Vec<_> = SplitQuoted::new(b"\"a\\\",b\",c", b',').collect();
+        assert_eq!(parts, [&b"\"a\\\",b\""[..], b"c"]);
+
+        let parts: Vec<_> = SplitQuoted::new(b"", b',').collect();
+        assert_eq!(parts, [&b""[..]]);
+    }
+
+    #[test]
+    fn test_trim_ows() {
+        assert_eq!(trim_ows(b" \ta b\t "), b"a b");
+        assert_eq!(trim_ows(b"  "), b"");
+        assert_eq!(trim_ows(b""), b"");
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote("abc"), "abc");
+        assert_eq!(unquote("\"abc\""), "abc");
+        assert_eq!(unquote("\"a\\\"b\\\\c\""), "a\"b\\c");
+        assert_eq!(unquote("\""), "\"");
+    }
+}
@@ -1,7 +1,9 @@
 use super::HeaderValue;
-use super::name::{HeaderName, HdrName, InvalidHeaderName};
+use super::name::{HeaderName, HdrName, InvalidHeaderName, SINGLE_VALUED_HEADERS};
 
-use std::{fmt, mem, ops, ptr, vec};
+use std::{cmp, fmt, io, mem, ops, ptr, str, vec};
+use std::error::Error;
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::RandomState;
 use std::hash::{BuildHasher, Hasher, Hash};
 use std::iter::FromIterator;
@@ -45,6 +47,12 @@ pub struct HeaderMap<T = HeaderValue> {
     entries: Vec<Bucket<T>>,
     extra_values: Vec<ExtraValue<T>>,
     danger: Danger,
+    growth: GrowthStrategy,
+    // The sender's casing of header names, for the names that have one. This
+    // is only allocated once a casing is recorded.
+    original_case: Option<Box<HashMap<HeaderName, String>>>,
+    // Whether names given as strings also have their casing recorded.
+    preserve_case: bool,
 }
 
 // # Implementation notes
@@ -81,6 +89,17 @@ pub struct Iter<'a, T: 'a> {
     inner: IterMut<'a, T>,
 }
 
+/// `HeaderMap` entry iterator yielding the original casing of header names.
+///
+/// Yields `(&str, &value)` tuples, in the same order as `Iter`. Names without
+/// a recorded original casing are yielded in their normalized, lowercase
+/// form.
+#[derive(Debug)]
+pub struct OriginalCaseIter<'a, T: 'a> {
+    inner: Iter<'a, T>,
+    original_case: Option<&'a HashMap<HeaderName, String>>,
+}
+
 /// `HeaderMap` mutable entry iterator
 ///
 /// Yields `(&HeaderName, &mut value)` tuples. The same header name may be
@@ -183,6 +202,15 @@ pub struct ValueIter<'a, T: 'a> {
     index: usize,
     front: Option<Cursor>,
     back: Option<Cursor>,
+    remaining: usize,
+}
+
+/// An iterator of the string values associated with a single header name.
+///
+/// Values that are not visible ASCII are skipped.
+#[derive(Debug)]
+pub struct GetAllStr<'a> {
+    inner: ValueIter<'a, HeaderValue>,
 }
 
 /// A mutable iterator of all values associated with a single header name.
@@ -204,6 +232,42 @@ pub struct ValueDrain<'a, T: 'a> {
     lt: PhantomData<&'a mut HeaderMap<T>>,
 }
 
+/// An error returned when a header name already holds the maximum number of
+/// allowed values.
+#[derive(Debug)]
+pub struct TooManyValues {
+    _priv: (),
+}
+
+/// An error returned when a `HeaderMap` already holds the maximum number of
+/// distinct header names.
+#[derive(Debug)]
+pub struct MaxSizeReached {
+    _priv: (),
+}
+
+/// An error returned by `HeaderMap::ensure_present` when required headers
+/// are absent.
+#[derive(Debug)]
+pub struct MissingHeaders {
+    names: Vec<HeaderName>,
+}
+
+/// How a `HeaderMap` grows the storage for its entries once it is full.
+///
+/// The index of a `HeaderMap` always grows to the next power of two. The
+/// storage for the entries themselves, which makes up most of the memory of
+/// the map, grows according to the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthStrategy {
+    /// Reserve room for as many entries as the index can hold, doubling the
+    /// storage each time the index doubles. This is the default.
+    Double,
+    /// Grow the storage by half of its length when it is full. This wastes
+    /// less memory in long-lived maps, at the cost of more reallocations.
+    OneAndAHalf,
+}
+
 /// Tracks the value iterator state
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Cursor {
@@ -259,11 +323,13 @@ struct Bucket<T> {
     links: Option<Links>,
 }
 
-/// The head and tail of the value linked list.
+/// The head and tail of the value linked list, and the number of values in
+/// it.
 #[derive(Debug, Copy, Clone)]
 struct Links {
     next: usize,
     tail: usize,
+    len: usize,
 }
 
 /// Node in doubly-linked list of header value entries
@@ -427,6 +493,285 @@ impl HeaderMap {
     pub fn new() -> Self {
         HeaderMap::with_capacity(0)
     }
+
+    /// Parse a `HeaderMap` from lines of the form `Name: Value`, as found in
+    /// configuration files or environment variables.
+    ///
+    /// Leading and trailing whitespace is trimmed from each line, name and
+    /// value. Blank lines and lines starting with `#` are skipped. Repeated
+    /// names are appended rather than replaced.
+    ///
+    /// An error is returned if a line has no `:`, or if its name or value is
+    /// invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let map = HeaderMap::from_env_style(vec![
+    ///     "# defaults",
+    ///     "Accept: text/html",
+    ///     "",
+    ///     "X-Trace:  on ",
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map["accept"], "text/html");
+    /// assert_eq!(map["x-trace"], "on");
+    /// ```
+    pub fn from_env_style<I, S>(lines: I) -> ::Result<HeaderMap>
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>,
+    {
+        let mut map = HeaderMap::new();
+
+        for line in lines {
+            let line = line.as_ref().trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let colon = match line.find(':') {
+                Some(i) => i,
+                None => return Err(InvalidHeaderName::new().into()),
+            };
+
+            let name = HeaderName::from_bytes(line[..colon].trim().as_bytes())?;
+            let value = HeaderValue::from_str(line[colon + 1..].trim())?;
+
+            map.append(name, value);
+        }
+
+        Ok(map)
+    }
+
+    /// Returns the first value associated with the key as a `&str`.
+    ///
+    /// `None` is returned if the key is absent, or if its value contains
+    /// bytes that are not visible ASCII, as determined by
+    /// `HeaderValue::to_str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{HeaderValue, HOST};
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_str(HOST).is_none());
+    ///
+    /// map.insert(HOST, "hello".parse().unwrap());
+    /// assert_eq!(map.get_str("host"), Some("hello"));
+    ///
+    /// map.insert(HOST, HeaderValue::from_bytes(b"h\xe9llo").unwrap());
+    /// assert!(map.get_str(HOST).is_none());
+    /// ```
+    pub fn get_str<K>(&self, key: K) -> Option<&str>
+        where K: AsHeaderName
+    {
+        self.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    /// Returns an iterator over all values associated with the key, as
+    /// `&str`s.
+    ///
+    /// Values that contain bytes that are not visible ASCII, as determined
+    /// by `HeaderValue::to_str`, are silently skipped. Use `get_all` to see
+    /// every value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{HeaderValue, ACCEPT};
+    /// let mut map = HeaderMap::new();
+    /// map.append(ACCEPT, "text/html".parse().unwrap());
+    /// map.append(ACCEPT, HeaderValue::from_bytes(b"text/\xfa").unwrap());
+    /// map.append(ACCEPT, "*/*".parse().unwrap());
+    ///
+    /// let values: Vec<_> = map.get_all_str(ACCEPT).collect();
+    /// assert_eq!(values, ["text/html", "*/*"]);
+    /// ```
+    pub fn get_all_str<'a, K>(&'a self, key: K) -> GetAllStr<'a>
+        where K: AsHeaderName
+    {
+        GetAllStr {
+            inner: self.get_all(key).into_iter(),
+        }
+    }
+
+    /// Returns `true` if both maps are equal, including the sensitivity flag
+    /// of each value.
+    ///
+    /// The `PartialEq` implementation ignores `HeaderValue::is_sensitive`.
+    /// This function additionally requires corresponding values to agree on
+    /// sensitivity, which is useful when verifying redaction behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{AUTHORIZATION, HeaderValue};
+    /// let mut a = HeaderMap::new();
+    /// a.insert(AUTHORIZATION, HeaderValue::from_static("secret"));
+    ///
+    /// let mut b = a.clone();
+    /// b.get_mut(AUTHORIZATION).unwrap().set_sensitive(true);
+    ///
+    /// assert_eq!(a, b);
+    /// assert!(!a.eq_with_sensitivity(&b));
+    /// ```
+    pub fn eq_with_sensitivity(&self, other: &HeaderMap) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.keys().all(|key| {
+            let mut a = self.get_all(key).iter();
+            let mut b = other.get_all(key).iter();
+
+            loop {
+                match (a.next(), b.next()) {
+                    (Some(a), Some(b)) => {
+                        if a != b || a.is_sensitive() != b.is_sensitive() {
+                            return false;
+                        }
+                    }
+                    (None, None) => return true,
+                    _ => return false,
+                }
+            }
+        })
+    }
+
+    /// Returns `true` if both maps are equal once the headers named in
+    /// `ignore` are disregarded.
+    ///
+    /// This is useful to compare messages that only differ in volatile
+    /// headers, such as `Date`. The values of every other header must be equal
+    /// and in the same order, as they would for `==`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{DATE, SERVER};
+    /// let mut a = HeaderMap::new();
+    /// a.insert(SERVER, "http".parse().unwrap());
+    /// a.insert(DATE, "Tue, 15 Nov 1994 08:12:31 GMT".parse().unwrap());
+    ///
+    /// let mut b = a.clone();
+    /// b.insert(DATE, "Wed, 16 Nov 1994 08:12:31 GMT".parse().unwrap());
+    ///
+    /// assert!(a != b);
+    /// assert!(a.eq_ignoring(&b, &[DATE]));
+    /// ```
+    pub fn eq_ignoring(&self, other: &HeaderMap, ignore: &[HeaderName]) -> bool {
+        let considered = |key: &&HeaderName| !ignore.contains(key);
+
+        if self.keys().filter(&considered).count() != other.keys().filter(&considered).count() {
+            return false;
+        }
+
+        self.keys().filter(&considered).all(|key| {
+            self.get_all(key).iter().eq(other.get_all(key).iter())
+        })
+    }
+
+    /// Serializes the map into an HTTP/1 header block.
+    ///
+    /// Each entry is rendered as `name: value\r\n`, in iteration order. Names
+    /// are written in lowercase, as stored. Values containing CR or LF are
+    /// skipped to guard against header injection; such values can only be
+    /// constructed through `HeaderValue::from_shared_unchecked`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONTENT_LENGTH, HOST};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.insert(CONTENT_LENGTH, "0".parse().unwrap());
+    ///
+    /// assert_eq!(
+    ///     map.to_wire_bytes(),
+    ///     &b"host: example.com\r\ncontent-length: 0\r\n"[..]);
+    /// ```
+    pub fn to_wire_bytes(&self) -> Vec<u8> {
+        let mut dst = Vec::new();
+        self.write_to(&mut dst).expect("writing to a Vec cannot fail");
+        dst
+    }
+
+    /// Writes the map as an HTTP/1 header block directly into `w`.
+    ///
+    /// This produces the same output as `to_wire_bytes`, without building an
+    /// intermediate buffer. Values containing CR or LF are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// let mut buf = Vec::new();
+    /// map.write_to(&mut buf).unwrap();
+    ///
+    /// assert_eq!(buf, &b"host: example.com\r\n"[..]);
+    /// ```
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        for (name, value) in self.iter() {
+            let value = value.as_bytes();
+
+            if value.iter().any(|&b| b == b'\r' || b == b'\n') {
+                continue;
+            }
+
+            w.write_all(name.as_str().as_bytes())?;
+            w.write_all(b": ")?;
+            w.write_all(value)?;
+            w.write_all(b"\r\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes equal values share a single buffer.
+    ///
+    /// Values parsed from separate messages each own their bytes, even when
+    /// they are identical, as is common for headers like `Cache-Control`.
+    /// After compaction, every value refers to the buffer of the first value
+    /// equal to it, and the other buffers are freed once no longer
+    /// referenced. The contents and sensitivity of the values are unchanged.
+    ///
+    /// Note that short values are stored inline and never share a buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CACHE_CONTROL, HeaderValue};
+    /// let value = "public, max-age=31536000, immutable";
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.append(CACHE_CONTROL, HeaderValue::from_str(value).unwrap());
+    /// map.append(CACHE_CONTROL, HeaderValue::from_str(value).unwrap());
+    ///
+    /// map.compact();
+    ///
+    /// let values: Vec<_> = map.get_all(CACHE_CONTROL).iter().collect();
+    /// assert_eq!(values[0].as_bytes().as_ptr(), values[1].as_bytes().as_ptr());
+    /// ```
+    pub fn compact(&mut self) {
+        let mut seen = HashSet::new();
+
+        for value in self.values_mut() {
+            value.share_storage(&mut seen);
+        }
+    }
 }
 
 impl<T> HeaderMap<T> {
@@ -458,6 +803,9 @@ impl<T> HeaderMap<T> {
                 entries: Vec::new(),
                 extra_values: Vec::new(),
                 danger: Danger::Green,
+                growth: GrowthStrategy::Double,
+                original_case: None,
+                preserve_case: false,
             }
         } else {
             let raw_cap = to_raw_capacity(capacity).next_power_of_two();
@@ -469,10 +817,73 @@ impl<T> HeaderMap<T> {
                 entries: Vec::with_capacity(raw_cap),
                 extra_values: Vec::new(),
                 danger: Danger::Green,
+                growth: GrowthStrategy::Double,
+                original_case: None,
+                preserve_case: false,
             }
         }
     }
 
+    /// Create an empty `HeaderMap` with the specified capacity and growth
+    /// strategy.
+    ///
+    /// With `GrowthStrategy::OneAndAHalf`, storage is allocated for exactly
+    /// `capacity` entries. Otherwise, this behaves like `with_capacity`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{GrowthStrategy, HeaderName};
+    /// let mut map: HeaderMap = HeaderMap::with_capacity_and_growth(10, GrowthStrategy::OneAndAHalf);
+    /// assert!(map.capacity() >= 10);
+    ///
+    /// for i in 0..100 {
+    ///     let name = HeaderName::from_bytes(format!("x-{}", i).as_bytes()).unwrap();
+    ///     map.insert(name, "a".parse().unwrap());
+    /// }
+    /// assert_eq!(map.len(), 100);
+    /// ```
+    pub fn with_capacity_and_growth(capacity: usize, growth: GrowthStrategy) -> HeaderMap<T> {
+        let mut map = HeaderMap::with_capacity(capacity);
+        map.growth = growth;
+
+        if growth == GrowthStrategy::OneAndAHalf {
+            map.entries = Vec::with_capacity(capacity);
+        }
+
+        map
+    }
+
+    /// Create an empty `HeaderMap` which records the casing of header names
+    /// inserted as strings.
+    ///
+    /// `HeaderName` is always lowercase. In this mode, names passed to
+    /// `insert`, `append`, `try_insert` and `try_append` as `&'static str`
+    /// additionally have their original casing stored, which can be
+    /// retrieved with `original_name` or `original_case_iter`. Inserting a
+    /// name again replaces its recorded casing.
+    ///
+    /// Names parsed at runtime should be inserted with
+    /// `insert_with_original_case` or `append_with_original_case`, which
+    /// record their casing in the same way on any map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let mut map: HeaderMap = HeaderMap::with_case_preservation();
+    /// map.insert("Content-Type", "text/plain".parse().unwrap());
+    ///
+    /// assert_eq!(map["content-type"], "text/plain");
+    /// assert_eq!(map.original_name("content-type"), Some("Content-Type"));
+    /// ```
+    pub fn with_case_preservation() -> HeaderMap<T> {
+        let mut map = HeaderMap::with_capacity(0);
+        map.preserve_case = true;
+        map
+    }
+
     /// Returns the number of headers stored in the map.
     ///
     /// This number represents the total number of **values** stored in the map.
@@ -566,6 +977,7 @@ impl<T> HeaderMap<T> {
         self.entries.clear();
         self.extra_values.clear();
         self.danger = Danger::Green;
+        self.original_case = None;
 
         for e in self.indices.iter_mut() {
             *e = Pos::none();
@@ -668,6 +1080,85 @@ impl<T> HeaderMap<T> {
         }
     }
 
+    /// Returns the first value associated with each of `keys`, in order.
+    ///
+    /// Each slot of the returned vector is `None` if the corresponding key
+    /// has no values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST, ORIGIN};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "hello".parse().unwrap());
+    /// map.insert(ACCEPT, "*/*".parse().unwrap());
+    ///
+    /// let values = map.get_many(&[ORIGIN, HOST, ACCEPT]);
+    /// assert!(values[0].is_none());
+    /// assert_eq!(values[1].unwrap(), "hello");
+    /// assert_eq!(values[2].unwrap(), "*/*");
+    /// ```
+    pub fn get_many<'a>(&'a self, keys: &[HeaderName]) -> Vec<Option<&'a T>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    /// Returns a reference to the first value associated with the key.
+    ///
+    /// This is equivalent to `get`, and is provided as the counterpart of
+    /// `get_last` for headers where the first occurrence wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "hello".parse().unwrap());
+    /// map.append(HOST, "world".parse().unwrap());
+    ///
+    /// assert_eq!(map.get_first(HOST).unwrap(), &"hello");
+    /// ```
+    #[inline]
+    pub fn get_first<K>(&self, key: K) -> Option<&T>
+        where K: AsHeaderName
+    {
+        self.get(key)
+    }
+
+    /// Returns a reference to the last value associated with the key.
+    ///
+    /// This is useful for headers where the last occurrence wins. Returns
+    /// `None` if there are no values associated with the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "hello".parse().unwrap());
+    /// assert_eq!(map.get_last(HOST).unwrap(), &"hello");
+    ///
+    /// map.append(HOST, "world".parse().unwrap());
+    /// assert_eq!(map.get_last(HOST).unwrap(), &"world");
+    /// ```
+    pub fn get_last<K>(&self, key: K) -> Option<&T>
+        where K: AsHeaderName
+    {
+        match key.find(self) {
+            Some((_, found)) => {
+                let entry = &self.entries[found];
+
+                match entry.links {
+                    Some(links) => Some(&self.extra_values[links.tail].value),
+                    None => Some(&entry.value),
+                }
+            }
+            None => None,
+        }
+    }
+
     /// Returns a mutable reference to the value associated with the key.
     ///
     /// If there are multiple values associated with the key, then the first one
@@ -750,6 +1241,38 @@ impl<T> HeaderMap<T> {
         key.find(self).is_some()
     }
 
+    /// Checks that the map contains a value for each of the `required`
+    /// header names.
+    ///
+    /// On failure, the returned error lists the missing names, in the order
+    /// they were given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONTENT_TYPE, HOST, USER_AGENT};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert!(map.ensure_present(&[HOST]).is_ok());
+    ///
+    /// let err = map.ensure_present(&[HOST, USER_AGENT, CONTENT_TYPE]).unwrap_err();
+    /// assert_eq!(err.names(), &[USER_AGENT, CONTENT_TYPE]);
+    /// ```
+    pub fn ensure_present(&self, required: &[HeaderName]) -> Result<(), MissingHeaders> {
+        let names: Vec<_> = required.iter()
+            .filter(|name| !self.contains_key(*name))
+            .cloned()
+            .collect();
+
+        if names.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingHeaders { names: names })
+        }
+    }
+
     /// An iterator visiting all key-value pairs.
     ///
     /// The iteration order is arbitrary, but consistent across platforms for
@@ -920,6 +1443,8 @@ impl<T> HeaderMap<T> {
             *i = Pos::none();
         }
 
+        self.original_case = None;
+
         Drain {
             idx: 0,
             map: self as *mut _,
@@ -927,23 +1452,177 @@ impl<T> HeaderMap<T> {
         }
     }
 
+    /// Retains only the values specified by the predicate.
+    ///
+    /// The predicate is called once for every value in the map, including
+    /// each value of a key with multiple values, and the value is removed
+    /// when it returns `false`. A key is removed once all of its values are.
+    /// The order of the remaining keys and values is preserved.
+    ///
+    /// Values are removed in place. If the predicate panics, the map is left
+    /// in a consistent state, though it may still hold values that were
+    /// already rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONNECTION, CONTENT_LENGTH, VIA};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONNECTION, "close".parse().unwrap());
+    /// map.insert(CONTENT_LENGTH, "0".parse().unwrap());
+    /// map.insert(VIA, "1.1 a".parse().unwrap());
+    /// map.append(VIA, "1.1 b".parse().unwrap());
+    ///
+    /// map.retain(|name, value| !name.is_hop_by_hop() && value != "1.1 a");
+    ///
+    /// assert!(!map.contains_key(CONNECTION));
+    /// assert_eq!(map.get_all(VIA).iter().collect::<Vec<_>>(), ["1.1 b"]);
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&HeaderName, &mut T) -> bool
+    {
+        let mut keep = vec![true; self.entries.len()];
+        let mut removed = 0;
+
+        for i in 0..self.entries.len() {
+            let keep_head = {
+                let entry = &mut self.entries[i];
+                f(&entry.key, &mut entry.value)
+            };
+
+            // Visit the rest of the values, unlinking the rejected ones.
+            let mut next = self.entries[i].links.map(|l| l.next);
+
+            while let Some(idx) = next {
+                let link = if f(&self.entries[i].key, &mut self.extra_values[idx].value) {
+                    self.extra_values[idx].next
+                } else {
+                    let extra = self.remove_extra_value(idx);
+
+                    // Values unlinked from the middle of the list don't
+                    // update the length of the entry, so do it here.
+                    if let (Link::Extra(_), Link::Extra(_)) = (extra.prev, extra.next) {
+                        self.entries[i].links.as_mut().unwrap().len -= 1;
+                    }
+
+                    extra.next
+                };
+
+                next = match link {
+                    Link::Extra(idx) => Some(idx),
+                    Link::Entry(_) => None,
+                };
+            }
+
+            // Only drop the first value once the others have been visited,
+            // replacing it with the next value if there is one.
+            if !keep_head {
+                match self.entries[i].links {
+                    Some(links) => {
+                        let extra = self.remove_extra_value(links.next);
+                        self.entries[i].value = extra.value;
+                    }
+                    None => {
+                        keep[i] = false;
+                        removed += 1;
+                    }
+                }
+            }
+        }
+
+        if removed == 0 {
+            return;
+        }
+
+        // Remove the emptied entries, keeping the order of the others.
+        {
+            let original_case = &mut self.original_case;
+            let mut keep = keep.into_iter();
+
+            self.entries.retain(|entry| {
+                let keep = keep.next().expect("a flag for every entry");
+
+                if !keep {
+                    if let Some(ref mut original_case) = *original_case {
+                        original_case.remove(&entry.key);
+                    }
+                }
+
+                keep
+            });
+        }
+
+        // Entries have moved, so point their value lists and the index table
+        // at their new positions.
+        for (index, entry) in self.entries.iter().enumerate() {
+            if let Some(links) = entry.links {
+                self.extra_values[links.next].prev = Link::Entry(index);
+                self.extra_values[links.tail].next = Link::Entry(index);
+            }
+        }
+
+        for pos in self.indices.iter_mut() {
+            *pos = Pos::none();
+        }
+
+        self.reindex();
+    }
+
+    /// Merges the headers of `other` into this map.
+    ///
+    /// Headers listed in `SINGLE_VALUED_HEADERS`, such as `Content-Type`,
+    /// replace any values already in this map. All other headers are
+    /// appended, as by `append`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONTENT_TYPE, SET_COOKIE};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    /// map.insert(SET_COOKIE, "a=1".parse().unwrap());
+    ///
+    /// let mut other = HeaderMap::new();
+    /// other.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    /// other.insert(SET_COOKIE, "b=2".parse().unwrap());
+    ///
+    /// map.merge_smart(other);
+    ///
+    /// assert_eq!(map.get_all(CONTENT_TYPE).iter().collect::<Vec<_>>(), ["text/html"]);
+    /// assert_eq!(map.get_all(SET_COOKIE).iter().collect::<Vec<_>>(), ["a=1", "b=2"]);
+    /// ```
+    pub fn merge_smart(&mut self, mut other: HeaderMap<T>) {
+        for (key, mut values) in other.drain() {
+            if SINGLE_VALUED_HEADERS.contains(&key) {
+                if let Some(value) = values.next() {
+                    self.insert(key.clone(), value);
+                }
+            }
+
+            for value in values {
+                self.append(key.clone(), value);
+            }
+        }
+    }
+
     fn value_iter(&self, idx: Option<usize>) -> ValueIter<T> {
         use self::Cursor::*;
 
         if let Some(idx) = idx {
-            let back = {
-                let entry = &self.entries[idx];
-
-                entry.links
-                    .map(|l| Values(l.tail))
-                    .unwrap_or(Head)
-            };
+            let links = self.entries[idx].links;
+            let back = links
+                .map(|l| Values(l.tail))
+                .unwrap_or(Head);
 
             ValueIter {
                 map: self,
                 index: idx,
                 front: Some(Head),
                 back: Some(back),
+                remaining: 1 + links.map(|l| l.len).unwrap_or(0),
             }
         } else {
             ValueIter {
@@ -951,6 +1630,7 @@ impl<T> HeaderMap<T> {
                 index: ::std::usize::MAX,
                 front: None,
                 back: None,
+                remaining: 0,
             }
         }
     }
@@ -978,6 +1658,9 @@ impl<T> HeaderMap<T> {
     /// Gets the given key's corresponding entry in the map for in-place
     /// manipulation.
     ///
+    /// The key may be a `HeaderName` or a string. A string is parsed as a
+    /// header name, which is why this returns an error if it is not valid.
+    ///
     /// # Examples
     ///
     /// ```
@@ -998,6 +1681,7 @@ impl<T> HeaderMap<T> {
     ///
     /// assert_eq!(map["content-length"], 2);
     /// assert_eq!(map["x-hello"], 1);
+    /// assert!(map.entry("x hello").is_err());
     /// ```
     pub fn entry<K>(&mut self, key: K) -> Result<Entry<T>, InvalidHeaderName>
         where K: AsHeaderName,
@@ -1073,6 +1757,40 @@ impl<T> HeaderMap<T> {
         key.insert(self, val)
     }
 
+    /// Inserts a key-value pair into the map, returning an error instead of
+    /// panicking if the map is full.
+    ///
+    /// A `HeaderMap` can hold at most 32,768 distinct header names; `insert`
+    /// panics when a new name would exceed that. This method is intended for
+    /// maps populated from untrusted input. Otherwise, it behaves like
+    /// `insert`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.try_insert(HOST, "world".parse().unwrap()).unwrap().is_none());
+    ///
+    /// let prev = map.try_insert(HOST, "earth".parse().unwrap()).unwrap();
+    /// assert_eq!(prev.unwrap(), "world");
+    /// ```
+    pub fn try_insert<K>(&mut self, key: K, val: T) -> Result<Option<T>, MaxSizeReached>
+        where K: IntoHeaderName,
+    {
+        key.try_insert(self, val)
+    }
+
+    #[inline]
+    fn try_insert2<K>(&mut self, key: K, value: T) -> Result<Option<T>, MaxSizeReached>
+        where K: Hash + Into<HeaderName>,
+              HeaderName: PartialEq<K>,
+    {
+        self.check_room(&key)?;
+        Ok(self.insert2(key, value))
+    }
+
     #[inline]
     fn insert2<K>(&mut self, key: K, value: T) -> Option<T>
         where K: Hash + Into<HeaderName>,
@@ -1126,44 +1844,348 @@ impl<T> HeaderMap<T> {
             links = entry.links.take();
         }
 
-        ValueDrain {
-            map: self as *mut _,
-            first: Some(old),
-            next: links.map(|l| l.next),
-            lt: PhantomData,
-        }
+        ValueDrain {
+            map: self as *mut _,
+            first: Some(old),
+            next: links.map(|l| l.next),
+            lt: PhantomData,
+        }
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not previously have this key present, then `false` is
+    /// returned.
+    ///
+    /// If the map did have this key present, the new value is pushed to the end
+    /// of the list of values currently associated with the key. The key is not
+    /// updated, though; this matters for types that can be `==` without being
+    /// identical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.insert(HOST, "world".parse().unwrap()).is_none());
+    /// assert!(!map.is_empty());
+    ///
+    /// map.append(HOST, "earth".parse().unwrap());
+    ///
+    /// let values = map.get_all("host");
+    /// let mut i = values.iter();
+    /// assert_eq!("world", *i.next().unwrap());
+    /// assert_eq!("earth", *i.next().unwrap());
+    /// ```
+    pub fn append<K>(&mut self, key: K, value: T) -> bool
+        where K: IntoHeaderName,
+    {
+        key.append(self, value)
+    }
+
+    /// Appends every value yielded by `values` to the list of values
+    /// associated with `key`.
+    ///
+    /// The key is looked up only once, rather than once per value as with
+    /// repeated calls to `append`. Values are appended in iteration order. If
+    /// `values` is empty, the map is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::SET_COOKIE;
+    /// let mut map = HeaderMap::new();
+    /// map.append_all(SET_COOKIE, vec![
+    ///     "a=1".parse().unwrap(),
+    ///     "b=2".parse().unwrap(),
+    /// ]);
+    ///
+    /// let cookies: Vec<_> = map.get_all(SET_COOKIE).iter().collect();
+    /// assert_eq!(cookies, ["a=1", "b=2"]);
+    /// ```
+    pub fn append_all<I>(&mut self, key: HeaderName, values: I)
+        where I: IntoIterator<Item = T>,
+    {
+        let mut values = values.into_iter();
+
+        let first = match values.next() {
+            Some(value) => value,
+            None => return,
+        };
+
+        let mut entry = match self.entry2(key) {
+            Entry::Occupied(mut e) => {
+                e.append(first);
+                e
+            }
+            Entry::Vacant(e) => e.insert_entry(first),
+        };
+
+        for value in values {
+            entry.append(value);
+        }
+    }
+
+    /// Appends a key-value pair into the map, unless the key already has
+    /// `max_per_name` associated values.
+    ///
+    /// This guards against a single header name accumulating an unbounded
+    /// number of values. On success, the return value has the same meaning as
+    /// for `append`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::VIA;
+    /// let mut map = HeaderMap::new();
+    /// assert!(!map.try_append_bounded(VIA, "a".parse().unwrap(), 2).unwrap());
+    /// assert!(map.try_append_bounded(VIA, "b".parse().unwrap(), 2).unwrap());
+    /// assert!(map.try_append_bounded(VIA, "c".parse().unwrap(), 2).is_err());
+    ///
+    /// assert_eq!(map.get_all(VIA).iter().count(), 2);
+    /// ```
+    pub fn try_append_bounded(&mut self, key: HeaderName, value: T, max_per_name: usize)
+        -> Result<bool, TooManyValues>
+    {
+        let count = self.get_all(&key).iter().take(max_per_name).count();
+
+        if count >= max_per_name {
+            return Err(TooManyValues { _priv: () });
+        }
+
+        Ok(self.append(key, value))
+    }
+
+    /// Appends a key-value pair into the map, returning an error instead of
+    /// panicking if the map is full.
+    ///
+    /// A `HeaderMap` can hold at most 32,768 distinct header names; `append`
+    /// panics when a new name would exceed that. Appending to a name that is
+    /// already present always succeeds. Otherwise, this behaves like
+    /// `append`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// assert!(!map.try_append(HOST, "world".parse().unwrap()).unwrap());
+    /// assert!(map.try_append(HOST, "earth".parse().unwrap()).unwrap());
+    ///
+    /// assert_eq!(map.get_all(HOST).iter().count(), 2);
+    /// ```
+    pub fn try_append<K>(&mut self, key: K, value: T) -> Result<bool, MaxSizeReached>
+        where K: IntoHeaderName,
+    {
+        key.try_append(self, value)
+    }
+
+    #[inline]
+    fn try_append2<K>(&mut self, key: K, value: T) -> Result<bool, MaxSizeReached>
+        where K: Hash + Into<HeaderName>,
+              HeaderName: PartialEq<K>,
+    {
+        self.check_room(&key)?;
+        Ok(self.append2(key, value))
+    }
+
+    /// Returns an error if inserting `key` would add a new entry to a map
+    /// that is already at `MAX_SIZE`.
+    #[inline]
+    fn check_room<K>(&self, key: &K) -> Result<(), MaxSizeReached>
+        where K: Hash + Into<HeaderName>,
+              HeaderName: PartialEq<K>,
+    {
+        if self.entries.len() >= MAX_SIZE && self.find(key).is_none() {
+            return Err(MaxSizeReached { _priv: () });
+        }
+
+        Ok(())
     }
 
-    /// Inserts a key-value pair into the map.
+    /// Inserts a key-value pair into the map, recording `original` as the
+    /// casing the sender used for `name`.
     ///
-    /// If the map did not previously have this key present, then `false` is
-    /// returned.
+    /// `HeaderName` is always lowercase, which loses the casing of the name
+    /// as it appeared on the wire. Some HTTP/1.1 peers depend on it, so it is
+    /// kept aside and yielded by `original_case_iter`. Lookups are not
+    /// affected and still use the lowercase name. The recorded casing is
+    /// dropped when the name is removed from the map.
     ///
-    /// If the map did have this key present, the new value is pushed to the end
-    /// of the list of values currently associated with the key. The key is not
-    /// updated, though; this matters for types that can be `==` without being
-    /// identical.
+    /// Otherwise, this behaves like `insert`.
+    ///
+    /// `original` may be given as a string or, as read from the wire, as
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `original` is not an ASCII case-insensitive
+    /// match for `name`.
     ///
     /// # Examples
     ///
     /// ```
     /// # use http::HeaderMap;
-    /// # use http::header::HOST;
+    /// # use http::header::CONTENT_TYPE;
     /// let mut map = HeaderMap::new();
-    /// assert!(map.insert(HOST, "world".parse().unwrap()).is_none());
-    /// assert!(!map.is_empty());
+    /// map.insert_with_original_case(CONTENT_TYPE, "Content-Type", "text/plain".parse().unwrap());
     ///
-    /// map.append(HOST, "earth".parse().unwrap());
+    /// assert_eq!(map["content-type"], "text/plain");
     ///
-    /// let values = map.get_all("host");
-    /// let mut i = values.iter();
-    /// assert_eq!("world", *i.next().unwrap());
-    /// assert_eq!("earth", *i.next().unwrap());
+    /// let (name, _) = map.original_case_iter().next().unwrap();
+    /// assert_eq!(name, "Content-Type");
     /// ```
-    pub fn append<K>(&mut self, key: K, value: T) -> bool
-        where K: IntoHeaderName,
+    pub fn insert_with_original_case<O>(&mut self, name: HeaderName, original: O, value: T)
+        -> Option<T>
+        where O: AsRef<[u8]>,
     {
-        key.append(self, value)
+        self.set_original_case(&name, original.as_ref());
+        self.insert(name, value)
+    }
+
+    /// Appends a key-value pair into the map, recording `original` as the
+    /// casing the sender used for `name`.
+    ///
+    /// This is the counterpart of `insert_with_original_case` for headers
+    /// that may be repeated, such as when copying the headers of a parsed
+    /// message. If the name is repeated with a different casing, the last one
+    /// is recorded.
+    ///
+    /// Otherwise, this behaves like `append`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `original` is not an ASCII case-insensitive
+    /// match for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HeaderName;
+    /// let mut map = HeaderMap::new();
+    ///
+    /// for &(raw, value) in &[(&b"X-Trace"[..], "a"), (b"X-Trace", "b")] {
+    ///     let name = HeaderName::from_bytes(raw).unwrap();
+    ///     map.append_with_original_case(name, raw, value.parse().unwrap());
+    /// }
+    ///
+    /// assert_eq!(map.get_all("x-trace").iter().count(), 2);
+    /// assert_eq!(map.original_name("x-trace"), Some("X-Trace"));
+    /// ```
+    pub fn append_with_original_case<O>(&mut self, name: HeaderName, original: O, value: T)
+        -> bool
+        where O: AsRef<[u8]>,
+    {
+        self.set_original_case(&name, original.as_ref());
+        self.append(name, value)
+    }
+
+    /// An iterator visiting all key-value pairs, with header names in the
+    /// casing recorded by `insert_with_original_case`.
+    ///
+    /// Names inserted any other way are yielded in lowercase. This is
+    /// intended for serializing the map to HTTP/1.1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONTENT_LENGTH, HeaderName};
+    /// let mut map = HeaderMap::new();
+    /// let custom = HeaderName::from_static("x-custom-header");
+    ///
+    /// map.insert_with_original_case(custom, "X-Custom-Header", "1".parse().unwrap());
+    /// map.insert(CONTENT_LENGTH, "0".parse().unwrap());
+    ///
+    /// let names: Vec<_> = map.original_case_iter().map(|(name, _)| name).collect();
+    /// assert_eq!(names, ["X-Custom-Header", "content-length"]);
+    /// ```
+    pub fn original_case_iter<'a>(&'a self) -> OriginalCaseIter<'a, T> {
+        OriginalCaseIter {
+            inner: self.iter(),
+            original_case: self.original_case.as_ref().map(|case| &**case),
+        }
+    }
+
+    /// Returns the casing recorded for the header name `key`.
+    ///
+    /// Casing is recorded by `insert_with_original_case` and
+    /// `append_with_original_case`, or by string keys on a map created with
+    /// `with_case_preservation`. `None` is returned if the map does not
+    /// contain `key` or no casing other than lowercase was recorded for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_LENGTH;
+    /// let mut map: HeaderMap = HeaderMap::with_case_preservation();
+    /// map.insert("X-Request-ID", "1".parse().unwrap());
+    /// map.insert(CONTENT_LENGTH, "0".parse().unwrap());
+    ///
+    /// assert_eq!(map.original_name("x-request-id"), Some("X-Request-ID"));
+    /// assert_eq!(map.original_name(CONTENT_LENGTH), None);
+    /// assert_eq!(map.original_name("host"), None);
+    /// ```
+    pub fn original_name<K>(&self, key: K) -> Option<&str>
+        where K: AsHeaderName
+    {
+        let original_case = match self.original_case {
+            Some(ref original_case) => original_case,
+            None => return None,
+        };
+
+        key.find(self)
+            .and_then(|(_, idx)| original_case.get(&self.entries[idx].key))
+            .map(|name| name.as_str())
+    }
+
+    /// Records `original` as the casing of `name`, replacing any previous
+    /// casing.
+    ///
+    /// Lowercase casings are not stored, as they are the same as `name`.
+    fn set_original_case(&mut self, name: &HeaderName, original: &[u8]) {
+        assert!(
+            original.eq_ignore_ascii_case(name.as_str().as_bytes()),
+            "original casing {:?} does not match header name {:?}",
+            String::from_utf8_lossy(original),
+            name.as_str());
+
+        if original == name.as_str().as_bytes() {
+            if let Some(ref mut original_case) = self.original_case {
+                original_case.remove(name);
+            }
+            return;
+        }
+
+        // `original` matches `name`, so it is ASCII.
+        let original = str::from_utf8(original).expect("header names are ASCII");
+
+        self.original_case
+            .get_or_insert_with(Default::default)
+            .insert(name.clone(), original.to_string());
+    }
+
+    /// Records `name` as the casing of the header it names, if this map
+    /// preserves case.
+    fn record_original_case(&mut self, name: &str) {
+        if !self.preserve_case {
+            return;
+        }
+
+        // A lowercase name only needs to clear a previously recorded casing.
+        if self.original_case.is_none() && !name.bytes().any(|b| b >= b'A' && b <= b'Z') {
+            return;
+        }
+
+        if let Ok(key) = HeaderName::from_bytes(name.as_bytes()) {
+            self.set_original_case(&key, name.as_bytes());
+        }
     }
 
     #[inline]
@@ -1303,6 +2325,10 @@ impl<T> HeaderMap<T> {
         self.indices[probe] = Pos::none();
         let entry = self.entries.swap_remove(found);
 
+        if let Some(ref mut original_case) = self.original_case {
+            original_case.remove(&entry.key);
+        }
+
         // correct index that points to the entry that had to swap places
         if let Some(entry) = self.entries.get(found) {
             // was not last element
@@ -1376,8 +2402,11 @@ impl<T> HeaderMap<T> {
                 debug_assert!(self.entries.len() > prev);
                 debug_assert!(self.entries[prev].links.is_some());
 
-                self.entries[prev].links.as_mut().unwrap()
-                    .next = next;
+                {
+                    let links = self.entries[prev].links.as_mut().unwrap();
+                    links.next = next;
+                    links.len -= 1;
+                }
 
                 debug_assert!(self.extra_values.len() > next);
                 self.extra_values[next].prev = Link::Entry(prev);
@@ -1386,8 +2415,11 @@ impl<T> HeaderMap<T> {
                 debug_assert!(self.entries.len() > next);
                 debug_assert!(self.entries[next].links.is_some());
 
-                self.entries[next].links.as_mut().unwrap()
-                    .tail = prev;
+                {
+                    let links = self.entries[next].links.as_mut().unwrap();
+                    links.tail = prev;
+                    links.len -= 1;
+                }
 
                 debug_assert!(self.extra_values.len() > prev);
                 self.extra_values[prev].next = Link::Entry(next);
@@ -1398,6 +2430,9 @@ impl<T> HeaderMap<T> {
 
                 self.extra_values[prev].next = Link::Extra(next);
                 self.extra_values[next].prev = Link::Extra(prev);
+
+                // The owning entry isn't known here, so the caller is
+                // responsible for decrementing its `links.len`.
             }
         }
 
@@ -1490,6 +2525,10 @@ impl<T> HeaderMap<T> {
     fn insert_entry(&mut self, hash: HashValue, key: HeaderName, value: T) {
         assert!(self.entries.len() < MAX_SIZE, "header map at capacity");
 
+        if self.entries.len() == self.entries.capacity() {
+            self.reserve_entries();
+        }
+
         self.entries.push(Bucket {
             hash: hash,
             key: key,
@@ -1499,16 +2538,24 @@ impl<T> HeaderMap<T> {
     }
 
     fn rebuild(&mut self) {
+        // Update the hash codes of all entries
+        for entry in self.entries.iter_mut() {
+            entry.hash = hash_elem_using(&self.danger, &entry.key);
+        }
+
+        self.reindex();
+    }
+
+    /// Inserts all entries into the cleared index table, using their stored
+    /// hash codes.
+    fn reindex(&mut self) {
         // Loop over all entries and re-insert them into the map
         'outer:
-        for (index, entry) in self.entries.iter_mut().enumerate() {
-            let hash = hash_elem_using(&self.danger, &entry.key);
+        for (index, entry) in self.entries.iter().enumerate() {
+            let hash = entry.hash;
             let mut probe = desired_pos(self.mask, hash);
             let mut dist = 0;
 
-            // Update the entry's hash code
-            entry.hash = hash;
-
             probe_loop!(probe < self.indices.len(), {
                 if let Some((_, entry_hash)) = self.indices[probe].resolve() {
                     // if existing element probed less than us, swap
@@ -1618,7 +2665,19 @@ impl<T> HeaderMap<T> {
         }
 
         // Reserve additional entry slots
-        let more = self.capacity() - self.entries.len();
+        self.reserve_entries();
+    }
+
+    /// Reserve entry slots according to the growth strategy.
+    fn reserve_entries(&mut self) {
+        let len = self.entries.len();
+        let room = self.capacity() - len;
+
+        let more = match self.growth {
+            GrowthStrategy::Double => room,
+            GrowthStrategy::OneAndAHalf => cmp::min(cmp::max(len / 2, 1), room),
+        };
+
         self.entries.reserve_exact(more);
     }
 }
@@ -1841,6 +2900,12 @@ impl<T: fmt::Debug> fmt::Debug for HeaderMap<T> {
     }
 }
 
+impl Default for GrowthStrategy {
+    fn default() -> GrowthStrategy {
+        GrowthStrategy::Double
+    }
+}
+
 impl<T> Default for HeaderMap<T> {
     fn default() -> Self {
         HeaderMap::with_capacity(0)
@@ -1905,6 +2970,7 @@ fn append_value<T>(entry_idx: usize,
 
             entry.links = Some(Links {
                 tail: idx,
+                len: links.len + 1,
                 .. links
             });
         }
@@ -1919,11 +2985,70 @@ fn append_value<T>(entry_idx: usize,
             entry.links = Some(Links {
                 next: idx,
                 tail: idx,
+                len: 1,
             });
         }
     }
 }
 
+// ===== impl TooManyValues =====
+
+impl fmt::Display for TooManyValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl Error for TooManyValues {
+    fn description(&self) -> &str {
+        "too many values for header name"
+    }
+}
+
+// ===== impl MaxSizeReached =====
+
+impl fmt::Display for MaxSizeReached {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("max header map size reached")
+    }
+}
+
+impl Error for MaxSizeReached {
+    fn description(&self) -> &str {
+        "max header map size reached"
+    }
+}
+
+// ===== impl MissingHeaders =====
+
+impl MissingHeaders {
+    /// Returns the names of the missing headers.
+    pub fn names(&self) -> &[HeaderName] {
+        &self.names
+    }
+}
+
+impl fmt::Display for MissingHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("missing required headers: ")?;
+
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(name.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for MissingHeaders {
+    fn description(&self) -> &str {
+        "missing required headers"
+    }
+}
+
 // ===== impl Iter =====
 
 impl<'a, T> Iterator for Iter<'a, T> {
@@ -1943,6 +3068,29 @@ impl<'a, T> Iterator for Iter<'a, T> {
 unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
 unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
 
+// ===== impl OriginalCaseIter =====
+
+impl<'a, T> Iterator for OriginalCaseIter<'a, T> {
+    type Item = (&'a str, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let original_case = self.original_case;
+
+        self.inner.next().map(|(key, value)| {
+            let name = original_case
+                .and_then(|case| case.get(key))
+                .map(|name| name.as_str())
+                .unwrap_or_else(|| key.as_str());
+
+            (name, value)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 // ===== impl IterMut =====
 
 impl<'a, T> IterMut<'a, T> {
@@ -2187,6 +3335,35 @@ impl<'a, T> Entry<'a, T> {
         }
     }
 
+    /// Provides in-place mutable access to the **first** value of an
+    /// occupied entry before any potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let mut map: HeaderMap<u32> = HeaderMap::default();
+    ///
+    /// for _ in 0..3 {
+    ///     map.entry("x-count").unwrap()
+    ///         .and_modify(|n| *n += 1)
+    ///         .or_insert(1);
+    /// }
+    ///
+    /// assert_eq!(map["x-count"], 3);
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Self {
+        use self::Entry::*;
+
+        match self {
+            Occupied(mut e) => {
+                f(e.get_mut());
+                Occupied(e)
+            }
+            Vacant(e) => Vacant(e),
+        }
+    }
+
     /// Returns a reference to the entry's key
     ///
     /// # Examples
@@ -2335,6 +3512,46 @@ impl<'a, T: 'a> GetAll<'a, T> {
             index: self.index,
         }.into_iter()
     }
+
+    /// Returns the number of values associated with the entry.
+    ///
+    /// The count is stored alongside the values, so this does not iterate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{HOST, VIA};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "hello.world".parse().unwrap());
+    /// map.append(HOST, "hello.earth".parse().unwrap());
+    ///
+    /// assert_eq!(map.get_all(HOST).len(), 2);
+    /// assert_eq!(map.get_all(VIA).len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        match self.index {
+            Some(idx) => 1 + self.map.entries[idx].links.map(|l| l.len).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Returns true if there are no values associated with the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_all(HOST).is_empty());
+    ///
+    /// map.insert(HOST, "hello.world".parse().unwrap());
+    /// assert!(!map.get_all(HOST).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.index.is_none()
+    }
 }
 
 impl<'a, T: PartialEq> PartialEq for GetAll<'a, T> {
@@ -2343,6 +3560,22 @@ impl<'a, T: PartialEq> PartialEq for GetAll<'a, T> {
     }
 }
 
+// ===== impl GetAllStr =====
+
+impl<'a> Iterator for GetAllStr<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while let Some(value) = self.inner.next() {
+            if let Ok(s) = value.to_str() {
+                return Some(s);
+            }
+        }
+
+        None
+    }
+}
+
 impl<'a, T> IntoIterator for GetAll<'a, T> {
     type Item = &'a T;
     type IntoIter = ValueIter<'a, T>;
@@ -2369,6 +3602,9 @@ impl<'a, T: 'a> Iterator for ValueIter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         use self::Cursor::*;
 
+        if self.front.is_some() {
+            self.remaining -= 1;
+        }
 
         match self.front {
             Some(Head) => {
@@ -2409,21 +3645,19 @@ impl<'a, T: 'a> Iterator for ValueIter<'a, T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        match (self.front, self.back) {
-            // Exactly 1 value...
-            (Some(Cursor::Head), Some(Cursor::Head)) => (1, Some(1)),
-            // At least 1...
-            (Some(_), _) => (1, None),
-            // No more values...
-            (None, _) => (0, Some(0)),
-        }
+        (self.remaining, Some(self.remaining))
     }
 }
 
+impl<'a, T: 'a> ExactSizeIterator for ValueIter<'a, T> {}
+
 impl<'a, T: 'a> DoubleEndedIterator for ValueIter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         use self::Cursor::*;
 
+        if self.back.is_some() {
+            self.remaining -= 1;
+        }
 
         match self.back {
             Some(Head) => {
@@ -3077,7 +4311,7 @@ fn hash_elem_using<K: ?Sized>(danger: &Danger, k: &K) -> HashValue
 
 
 mod into_header_name {
-    use super::{HdrName, HeaderMap, HeaderName};
+    use super::{HdrName, HeaderMap, HeaderName, MaxSizeReached};
 
     /// A marker trait used to identify values that can be used as insert keys
     /// to a `HeaderMap`.
@@ -3097,6 +4331,14 @@ mod into_header_name {
 
         #[doc(hidden)]
         fn append<T>(self, map: &mut HeaderMap<T>, val: T) -> bool;
+
+        #[doc(hidden)]
+        fn try_insert<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<Option<T>, MaxSizeReached>;
+
+        #[doc(hidden)]
+        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<bool, MaxSizeReached>;
     }
 
     // ==== impls ====
@@ -3113,6 +4355,22 @@ mod into_header_name {
         fn append<T>(self, map: &mut HeaderMap<T>, val: T) -> bool {
             map.append2(self, val)
         }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_insert<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<Option<T>, MaxSizeReached>
+        {
+            map.try_insert2(self, val)
+        }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<bool, MaxSizeReached>
+        {
+            map.try_append2(self, val)
+        }
     }
 
     impl IntoHeaderName for HeaderName {}
@@ -3128,6 +4386,22 @@ mod into_header_name {
         fn append<T>(self, map: &mut HeaderMap<T>, val: T) -> bool {
             map.append2(self, val)
         }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_insert<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<Option<T>, MaxSizeReached>
+        {
+            map.try_insert2(self, val)
+        }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<bool, MaxSizeReached>
+        {
+            map.try_append2(self, val)
+        }
     }
 
     impl<'a> IntoHeaderName for &'a HeaderName {}
@@ -3136,12 +4410,36 @@ mod into_header_name {
         #[doc(hidden)]
         #[inline]
         fn insert<T>(self, map: &mut HeaderMap<T>, val: T) -> Option<T> {
-            HdrName::from_static(self, move |hdr| map.insert2(hdr, val))
+            let prev = HdrName::from_static(self, |hdr| map.insert2(hdr, val));
+            map.record_original_case(self);
+            prev
         }
         #[doc(hidden)]
         #[inline]
         fn append<T>(self, map: &mut HeaderMap<T>, val: T) -> bool {
-            HdrName::from_static(self, move |hdr| map.append2(hdr, val))
+            let existed = HdrName::from_static(self, |hdr| map.append2(hdr, val));
+            map.record_original_case(self);
+            existed
+        }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_insert<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<Option<T>, MaxSizeReached>
+        {
+            let prev = HdrName::from_static(self, |hdr| map.try_insert2(hdr, val))?;
+            map.record_original_case(self);
+            Ok(prev)
+        }
+
+        #[doc(hidden)]
+        #[inline]
+        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T)
+            -> Result<bool, MaxSizeReached>
+        {
+            let existed = HdrName::from_static(self, |hdr| map.try_append2(hdr, val))?;
+            map.record_original_case(self);
+            Ok(existed)
         }
     }
 
@@ -3282,3 +4580,23 @@ fn skip_duplicates_during_key_iteration() {
     map.append("a", HeaderValue::from_static("b"));
     assert_eq!(map.keys().count(), map.keys_len());
 }
+
+#[test]
+fn test_growth_strategy() {
+    for &growth in &[GrowthStrategy::Double, GrowthStrategy::OneAndAHalf] {
+        let mut map = HeaderMap::with_capacity_and_growth(0, growth);
+
+        for i in 0..2000 {
+            let name = HeaderName::from_bytes(format!("x-{}", i).as_bytes()).unwrap();
+            map.insert(name, i);
+
+            let len = map.entries.len();
+            let cap = map.entries.capacity();
+
+            match growth {
+                GrowthStrategy::Double => assert_eq!(cap, map.capacity()),
+                GrowthStrategy::OneAndAHalf => assert!(cap <= cmp::max(6, len + len / 2)),
+            }
+        }
+    }
+}
@@ -70,10 +70,26 @@
 //! [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
 //! [Robin Hood hashing]: https://en.wikipedia.org/wiki/Hash_table#Robin_Hood_hashing
 
+mod extract;
+mod framing;
+mod list;
 mod map;
 mod name;
+mod qvalue;
+mod range;
+#[cfg(feature = "typed-headers")]
+mod typed;
 mod value;
+#[cfg(feature = "serde")]
+mod value_serde;
 
+pub use self::extract::{
+    FromHeaders,
+    HeaderError,
+    optional,
+    require,
+};
+pub use self::framing::InvalidContentLength;
 pub use self::map::{
     HeaderMap,
     AsHeaderName,
@@ -85,6 +101,7 @@ pub use self::map::{
     ValuesMut,
     Drain,
     GetAll,
+    GetAllStr,
     Entry,
     VacantEntry,
     OccupiedEntry,
@@ -92,17 +109,39 @@ pub use self::map::{
     ValueIterMut,
     ValueDrain,
     IntoIter,
+    GrowthStrategy,
+    OriginalCaseIter,
+    TooManyValues,
+    MaxSizeReached,
+    MissingHeaders,
 };
 pub use self::name::{
     HeaderName,
     InvalidHeaderName,
     InvalidHeaderNameBytes,
+    HOP_BY_HOP_HEADERS,
+    SINGLE_VALUED_HEADERS,
+};
+pub use self::qvalue::QValue;
+pub use self::range::{ByteRange, ContentRange};
+#[cfg(feature = "typed-headers")]
+pub use self::typed::{
+    ContentLength,
+    ContentType,
+    Header,
+    HeaderMapExt,
 };
 pub use self::value::{
+    AuthCredentials,
     HeaderValue,
+    HtmlEscaped,
     InvalidHeaderValue,
     InvalidHeaderValueBytes,
+    ParseError,
+    SplitCommaQuoted,
+    SplitCommas,
     ToStrError,
+    VaryResult,
 };
 
 // Use header name constants
@@ -149,6 +188,7 @@ pub use self::name::{
     IF_NONE_MATCH,
     IF_RANGE,
     IF_UNMODIFIED_SINCE,
+    KEEP_ALIVE,
     LAST_MODIFIED,
     LINK,
     LOCATION,
@@ -194,3 +234,25 @@ pub use self::name::{
 /// in practice. Restricting it to this size enables using `u16` values to
 /// represent offsets when dealing with header names.
 const MAX_HEADER_NAME_LEN: usize = 1 << 16;
+
+/// Parses the `Connection` header fields of `headers` into the additional
+/// hop-by-hop header names they list.
+pub(crate) fn connection_listed_headers(headers: &HeaderMap) -> Vec<HeaderName> {
+    let mut names = Vec::new();
+
+    for value in headers.get_all(CONNECTION) {
+        for token in value.as_bytes().split(|&b| b == b',') {
+            let token = list::trim_ows(token);
+
+            if let Ok(name) = HeaderName::from_bytes(token) {
+                if !name.is_hop_by_hop() && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+
+    names
+}
+
+pub(crate) use self::framing::{content_length, is_chunked};
@@ -662,6 +662,10 @@ standard_headers! {
     /// Content-Types that are acceptable for the response.
     (LastModified, LAST_MODIFIED, "last-modified");
 
+    /// Hints how long an idle connection may be kept open and how many
+    /// requests it may serve.
+    (KeepAlive, KEEP_ALIVE, "keep-alive");
+
     /// Allows the server to point an interested client to another resource
     /// containing metadata about the requested resource.
     (Link, LINK, "link");
@@ -969,6 +973,57 @@ standard_headers! {
     (XXssProtection, X_XSS_PROTECTION, "x-xss-protection");
 }
 
+/// The standard hop-by-hop headers.
+///
+/// These headers are meaningful only for a single transport-level connection
+/// and must not be forwarded by proxies, as described in [RFC 7230, Section
+/// 6.1]. Note that the `Connection` header may list additional hop-by-hop
+/// headers, so its value must be consulted separately.
+///
+/// [RFC 7230, Section 6.1]: https://tools.ietf.org/html/rfc7230#section-6.1
+pub const HOP_BY_HOP_HEADERS: &'static [HeaderName] = &[
+    CONNECTION,
+    KEEP_ALIVE,
+    PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION,
+    TE,
+    TRAILER,
+    TRANSFER_ENCODING,
+    UPGRADE,
+];
+
+/// The standard headers that hold a single value.
+///
+/// These headers are not defined as comma-separated lists, so a message
+/// carries at most one of each; `Set-Cookie`, for instance, is not among
+/// them. Merging two sets of headers should replace these rather than append
+/// to them, as `HeaderMap::merge_smart` does.
+pub const SINGLE_VALUED_HEADERS: &'static [HeaderName] = &[
+    AGE,
+    AUTHORIZATION,
+    CONTENT_DISPOSITION,
+    CONTENT_LENGTH,
+    CONTENT_LOCATION,
+    CONTENT_RANGE,
+    CONTENT_TYPE,
+    DATE,
+    ETAG,
+    EXPIRES,
+    FROM,
+    HOST,
+    IF_MODIFIED_SINCE,
+    IF_RANGE,
+    IF_UNMODIFIED_SINCE,
+    LAST_MODIFIED,
+    LOCATION,
+    MAX_FORWARDS,
+    PROXY_AUTHORIZATION,
+    REFERER,
+    RETRY_AFTER,
+    SERVER,
+    USER_AGENT,
+];
+
 /// Valid header name characters
 ///
 /// ```not_rust
@@ -1244,6 +1299,8 @@ fn parse_hdr<'a>(data: &'a [u8], b: &'a mut [u8; 64], table: &[u8; 256])
 
             if eq!(b == b'c' b'o' b'n' b'n' b'e' b'c' b't' b'i' b'o' b'n') {
                 Ok(Connection.into())
+            } else if eq!(b == b'k' b'e' b'e' b'p' b'-' b'a' b'l' b'i' b'v' b'e') {
+                Ok(KeepAlive.into())
             } else if eq!(b == b's' b'e' b't' b'-' b'c' b'o' b'o' b'k' b'i' b'e') {
                 Ok(SetCookie.into())
             } else if eq!(b == b'u' b's' b'e' b'r' b'-' b'a' b'g' b'e' b'n' b't') {
@@ -1674,6 +1731,35 @@ impl HeaderName {
             Repr::Custom(ref v) => &*v.0,
         }
     }
+
+    /// Returns `true` if this is one of the standard hop-by-hop headers
+    /// listed in `HOP_BY_HOP_HEADERS`.
+    ///
+    /// Headers listed in the value of a `Connection` header are hop-by-hop as
+    /// well, but those must be checked separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::*;
+    /// assert!(TRANSFER_ENCODING.is_hop_by_hop());
+    /// assert!(!CONTENT_TYPE.is_hop_by_hop());
+    /// ```
+    pub fn is_hop_by_hop(&self) -> bool {
+        use self::StandardHeader::*;
+
+        match self.inner {
+            Repr::Standard(Connection) |
+            Repr::Standard(KeepAlive) |
+            Repr::Standard(ProxyAuthenticate) |
+            Repr::Standard(ProxyAuthorization) |
+            Repr::Standard(Te) |
+            Repr::Standard(Trailer) |
+            Repr::Standard(TransferEncoding) |
+            Repr::Standard(Upgrade) => true,
+            _ => false,
+        }
+    }
 }
 
 impl FromStr for HeaderName {
@@ -1718,7 +1804,7 @@ impl fmt::Display for HeaderName {
 }
 
 impl InvalidHeaderName {
-    fn new() -> InvalidHeaderName {
+    pub(super) fn new() -> InvalidHeaderName {
         InvalidHeaderName { _priv: () }
     }
 }
@@ -2203,4 +2289,18 @@ mod tests {
     fn test_all_tokens() {
         HeaderName::from_static("!#$%&'*+-.^_`|~0123456789abcdefghijklmnopqrstuvwxyz");
     }
+
+    #[test]
+    fn test_hop_by_hop() {
+        for name in HOP_BY_HOP_HEADERS {
+            assert!(name.is_hop_by_hop(), "{:?}", name);
+        }
+        assert_eq!(HOP_BY_HOP_HEADERS.len(), 8);
+
+        assert!(HeaderName::from_static("keep-alive").is_hop_by_hop());
+        assert!(HeaderName::from_bytes(b"Transfer-Encoding").unwrap().is_hop_by_hop());
+
+        assert!(!CONTENT_TYPE.is_hop_by_hop());
+        assert!(!HeaderName::from_static("x-connection").is_hop_by_hop());
+    }
 }
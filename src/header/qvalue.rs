@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// A quality value, as used by content negotiation headers.
+///
+/// Quality values are decimal numbers between 0 and 1 with at most three
+/// digits after the decimal point, as described in [RFC 7231, Section
+/// 5.3.1]. They are stored as an integer number of thousandths.
+///
+/// [RFC 7231, Section 5.3.1]: https://tools.ietf.org/html/rfc7231#section-5.3.1
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::QValue;
+/// let q = QValue::from_thousandths(900).unwrap();
+///
+/// assert_eq!(q.as_thousandths(), 900);
+/// assert_eq!(q.to_string(), "0.9");
+/// assert_eq!(QValue::default().as_thousandths(), 1000);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QValue(u16);
+
+impl QValue {
+    /// Create a `QValue` from a number of thousandths.
+    ///
+    /// Returns `None` if `value` is greater than 1000.
+    pub fn from_thousandths(value: u16) -> Option<QValue> {
+        if value > 1000 {
+            None
+        } else {
+            Some(QValue(value))
+        }
+    }
+
+    /// Returns the quality as a number of thousandths, between 0 and 1000.
+    #[inline]
+    pub fn as_thousandths(&self) -> u16 {
+        self.0
+    }
+
+    /// Parse a `qvalue` as defined by RFC 7231.
+    pub(crate) fn parse(s: &str) -> Option<QValue> {
+        let bytes = s.as_bytes();
+
+        let (int, frac) = match bytes.split_first() {
+            Some((&b'0', rest)) => (0, rest),
+            Some((&b'1', rest)) => (1000, rest),
+            _ => return None,
+        };
+
+        let frac = match frac.split_first() {
+            None => return Some(QValue(int)),
+            Some((&b'.', frac)) => frac,
+            _ => return None,
+        };
+
+        if frac.len() > 3 {
+            return None;
+        }
+
+        let mut value = 0;
+        let mut scale = 100;
+
+        for &b in frac {
+            if b < b'0' || b > b'9' {
+                return None;
+            }
+            value += (b - b'0') as u16 * scale;
+            scale /= 10;
+        }
+
+        QValue::from_thousandths(int + value)
+    }
+}
+
+/// Returns a quality of 1, the implied value when none is given.
+impl Default for QValue {
+    fn default() -> QValue {
+        QValue(1000)
+    }
+}
+
+impl fmt::Debug for QValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for QValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            1000 => f.write_str("1"),
+            0 => f.write_str("0"),
+            mut v => {
+                let mut digits = 3;
+                while v % 10 == 0 {
+                    v /= 10;
+                    digits -= 1;
+                }
+                write!(f, "0.{:0width$}", v, width = digits)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(QValue::parse("1"), Some(QValue(1000)));
+        assert_eq!(QValue::parse("1.000"), Some(QValue(1000)));
+        assert_eq!(QValue::parse("0"), Some(QValue(0)));
+        assert_eq!(QValue::parse("0."), Some(QValue(0)));
+        assert_eq!(QValue::parse("0.8"), Some(QValue(800)));
+        assert_eq!(QValue::parse("0.05"), Some(QValue(50)));
+        assert_eq!(QValue::parse("0.001"), Some(QValue(1)));
+
+        assert_eq!(QValue::parse(""), None);
+        assert_eq!(QValue::parse("1.1"), None);
+        assert_eq!(QValue::parse("0.0001"), None);
+        assert_eq!(QValue::parse("2"), None);
+        assert_eq!(QValue::parse(".5"), None);
+        assert_eq!(QValue::parse("0,5"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(QValue(1000).to_string(), "1");
+        assert_eq!(QValue(0).to_string(), "0");
+        assert_eq!(QValue(500).to_string(), "0.5");
+        assert_eq!(QValue(50).to_string(), "0.05");
+        assert_eq!(QValue(123).to_string(), "0.123");
+    }
+}
@@ -0,0 +1,281 @@
+use super::framing;
+use super::list;
+
+/// A byte range, as requested by the `Range` header.
+///
+/// Ranges are described in [RFC 7233, Section 2.1]. Positions are zero-based
+/// and `Inclusive` ranges include their last byte.
+///
+/// [RFC 7233, Section 2.1]: https://tools.ietf.org/html/rfc7233#section-2.1
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::{ByteRange, HeaderValue};
+/// let val = HeaderValue::from_static("bytes=0-499, 1000-, -200");
+///
+/// assert_eq!(val.parse_byte_ranges(None).unwrap(), [
+///     ByteRange::Inclusive(0, 499),
+///     ByteRange::From(1000),
+///     ByteRange::Suffix(200),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// The bytes from the first position to the last position, inclusive.
+    Inclusive(u64, u64),
+    /// The bytes from the given position to the end of the resource.
+    From(u64),
+    /// The given number of bytes at the end of the resource.
+    Suffix(u64),
+}
+
+/// The value of a `Content-Range` header in the `bytes` unit.
+///
+/// The syntax is described in [RFC 7233, Section 4.2]. Positions are
+/// zero-based and the range includes its last byte.
+///
+/// [RFC 7233, Section 4.2]: https://tools.ietf.org/html/rfc7233#section-4.2
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::{ContentRange, HeaderValue};
+/// let val = HeaderValue::from_static("bytes 0-499/1234");
+///
+/// assert_eq!(val.parse_content_range().unwrap(), ContentRange {
+///     range: Some((0, 499)),
+///     complete_length: Some(1234),
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentRange {
+    /// The first and last positions of the enclosed bytes, or `None` for an
+    /// unsatisfied range, as in `bytes */1234`.
+    pub range: Option<(u64, u64)>,
+    /// The length of the complete representation, or `None` if it is
+    /// unknown, as in `bytes 0-499/*`.
+    pub complete_length: Option<u64>,
+}
+
+/// Parses a `Range` header value in the `bytes` unit.
+///
+/// When `resource_len` is known, every range is resolved to an `Inclusive`
+/// range within the resource and unsatisfiable ranges are dropped. `None` is
+/// returned if the value is malformed or if no range is satisfiable.
+pub(crate) fn parse(value: &[u8], resource_len: Option<u64>) -> Option<Vec<ByteRange>> {
+    let value = list::trim_ows(value);
+
+    if value.len() < 6 || !value[..6].eq_ignore_ascii_case(b"bytes=") {
+        return None;
+    }
+
+    let mut ranges = Vec::new();
+    let mut specs = 0;
+
+    for spec in value[6..].split(|&b| b == b',') {
+        let spec = list::trim_ows(spec);
+
+        if spec.is_empty() {
+            continue;
+        }
+
+        specs += 1;
+
+        let range = parse_spec(spec)?;
+
+        match resource_len {
+            Some(len) => ranges.extend(resolve(range, len)),
+            None => ranges.push(range),
+        }
+    }
+
+    if specs == 0 || ranges.is_empty() {
+        return None;
+    }
+
+    Some(ranges)
+}
+
+/// Parses a `Content-Range` header value in the `bytes` unit.
+///
+/// `None` is returned if the value is malformed, or if the range is not
+/// within the complete length.
+pub(crate) fn parse_content_range(value: &[u8]) -> Option<ContentRange> {
+    let value = list::trim_ows(value);
+
+    if value.len() < 6 || !value[..6].eq_ignore_ascii_case(b"bytes ") {
+        return None;
+    }
+
+    let value = &value[6..];
+    let slash = value.iter().position(|&b| b == b'/')?;
+    let (range, complete_length) = (&value[..slash], &value[slash + 1..]);
+
+    let complete_length = match complete_length {
+        b"*" => None,
+        digits => Some(number(digits)?),
+    };
+
+    let range = match range {
+        // An unsatisfied range must state the complete length.
+        b"*" => {
+            complete_length?;
+            None
+        }
+        range => {
+            let dash = range.iter().position(|&b| b == b'-')?;
+            let first = number(&range[..dash])?;
+            let last = number(&range[dash + 1..])?;
+
+            if last < first || complete_length.map_or(false, |len| last >= len) {
+                return None;
+            }
+
+            Some((first, last))
+        }
+    };
+
+    Some(ContentRange {
+        range: range,
+        complete_length: complete_length,
+    })
+}
+
+fn parse_spec(spec: &[u8]) -> Option<ByteRange> {
+    let dash = spec.iter().position(|&b| b == b'-')?;
+    let (first, last) = (&spec[..dash], &spec[dash + 1..]);
+
+    if first.is_empty() {
+        return number(last).map(ByteRange::Suffix);
+    }
+
+    let first = number(first)?;
+
+    if last.is_empty() {
+        return Some(ByteRange::From(first));
+    }
+
+    let last = number(last)?;
+
+    if last < first {
+        return None;
+    }
+
+    Some(ByteRange::Inclusive(first, last))
+}
+
+fn number(digits: &[u8]) -> Option<u64> {
+    framing::parse_length(digits).ok()
+}
+
+/// Resolves `range` against a resource of `len` bytes, returning `None` if
+/// it is unsatisfiable.
+fn resolve(range: ByteRange, len: u64) -> Option<ByteRange> {
+    let (first, last) = match range {
+        ByteRange::Inclusive(first, last) => (first, last),
+        ByteRange::From(first) => (first, ::std::u64::MAX),
+        ByteRange::Suffix(0) => return None,
+        ByteRange::Suffix(n) => (len.saturating_sub(n), ::std::u64::MAX),
+    };
+
+    if first >= len {
+        return None;
+    }
+
+    Some(ByteRange::Inclusive(first, last.min(len - 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::ByteRange::*;
+
+    fn ranges(s: &str, len: Option<u64>) -> Option<Vec<ByteRange>> {
+        parse(s.as_bytes(), len)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ranges("bytes=0-499", None).unwrap(), [Inclusive(0, 499)]);
+        assert_eq!(ranges("bytes=500-", None).unwrap(), [From(500)]);
+        assert_eq!(ranges("bytes=-500", None).unwrap(), [Suffix(500)]);
+        assert_eq!(ranges("Bytes=0-0,-1", None).unwrap(), [Inclusive(0, 0), Suffix(1)]);
+        assert_eq!(ranges("bytes= 0-1 , ,2-3", None).unwrap(),
+                   [Inclusive(0, 1), Inclusive(2, 3)]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let len = Some(10_000);
+
+        assert_eq!(ranges("bytes=0-499", len).unwrap(), [Inclusive(0, 499)]);
+        assert_eq!(ranges("bytes=9500-", len).unwrap(), [Inclusive(9500, 9999)]);
+        assert_eq!(ranges("bytes=-500", len).unwrap(), [Inclusive(9500, 9999)]);
+        assert_eq!(ranges("bytes=-20000", len).unwrap(), [Inclusive(0, 9999)]);
+        assert_eq!(ranges("bytes=9000-20000", len).unwrap(), [Inclusive(9000, 9999)]);
+
+        // Unsatisfiable ranges are dropped, unless none is left.
+        assert_eq!(ranges("bytes=0-0,10000-,-0", len).unwrap(), [Inclusive(0, 0)]);
+        assert!(ranges("bytes=10000-", len).is_none());
+        assert!(ranges("bytes=-0", len).is_none());
+        assert!(ranges("bytes=0-", Some(0)).is_none());
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        fn content_range(s: &str) -> Option<ContentRange> {
+            parse_content_range(s.as_bytes())
+        }
+
+        fn cr(range: Option<(u64, u64)>, complete_length: Option<u64>) -> Option<ContentRange> {
+            Some(ContentRange { range: range, complete_length: complete_length })
+        }
+
+        assert_eq!(content_range("bytes 0-499/1234"), cr(Some((0, 499)), Some(1234)));
+        assert_eq!(content_range("bytes 1233-1233/1234"), cr(Some((1233, 1233)), Some(1234)));
+        assert_eq!(content_range("bytes 0-499/*"), cr(Some((0, 499)), None));
+        assert_eq!(content_range("bytes */1234"), cr(None, Some(1234)));
+        assert_eq!(content_range(" Bytes 0-0/1 "), cr(Some((0, 0)), Some(1)));
+
+        let invalid = [
+            "",
+            "bytes",
+            "bytes */*",
+            "bytes 0-499",
+            "bytes 0-/1234",
+            "bytes -499/1234",
+            "bytes 500-499/1234",
+            "bytes 0-1234/1234",
+            "bytes 0-499/12a",
+            "bytes  0-499/1234",
+            "bytes=0-499/1234",
+            "items 0-499/1234",
+        ];
+
+        for s in invalid.iter() {
+            assert!(content_range(s).is_none(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let invalid = [
+            "",
+            "bytes=",
+            "bytes=,",
+            "bytes=-",
+            "bytes=5-4",
+            "bytes=a-b",
+            "bytes=0-1,x",
+            "bytes=+1-2",
+            "bytes 0-1",
+            "items=0-1",
+            "bytes=18446744073709551616-",
+        ];
+
+        for s in invalid.iter() {
+            assert!(ranges(s, None).is_none(), "{:?}", s);
+        }
+    }
+}
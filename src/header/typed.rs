@@ -0,0 +1,143 @@
+//! Typed access to well-known headers, enabled by the `typed-headers`
+//! feature.
+
+use super::{HeaderError, HeaderMap, HeaderName, HeaderValue};
+use super::{CONTENT_LENGTH, CONTENT_TYPE};
+use super::framing;
+use super::list;
+
+/// A header with a typed representation.
+///
+/// Implementations know the name of their header, and how to convert
+/// between the typed representation and a `HeaderValue`.
+pub trait Header: Sized {
+    /// Returns the name of the header.
+    fn name() -> HeaderName;
+
+    /// Decodes the typed representation from a header value.
+    fn decode(value: &HeaderValue) -> Result<Self, HeaderError>;
+
+    /// Encodes the typed representation into a header value.
+    fn encode(&self) -> HeaderValue;
+}
+
+/// Extension methods for typed access to a `HeaderMap`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderMap;
+/// use http::header::{ContentLength, HeaderMapExt};
+///
+/// let mut map = HeaderMap::new();
+/// map.typed_insert(ContentLength(1234));
+///
+/// assert_eq!(map["content-length"], "1234");
+/// assert_eq!(map.typed_get::<ContentLength>(), Some(ContentLength(1234)));
+/// ```
+pub trait HeaderMapExt {
+    /// Returns the typed value of the header `H`.
+    ///
+    /// `None` is returned if the header is absent or if its first value
+    /// cannot be decoded.
+    fn typed_get<H: Header>(&self) -> Option<H>;
+
+    /// Inserts the header `H`, replacing any previous values.
+    fn typed_insert<H: Header>(&mut self, header: H);
+}
+
+impl HeaderMapExt for HeaderMap {
+    fn typed_get<H: Header>(&self) -> Option<H> {
+        self.get(H::name()).and_then(|value| H::decode(value).ok())
+    }
+
+    fn typed_insert<H: Header>(&mut self, header: H) {
+        self.insert(H::name(), header.encode());
+    }
+}
+
+/// The `Content-Length` header, the size of the message body in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLength(pub u64);
+
+impl Header for ContentLength {
+    fn name() -> HeaderName {
+        CONTENT_LENGTH
+    }
+
+    fn decode(value: &HeaderValue) -> Result<ContentLength, HeaderError> {
+        framing::parse_length(list::trim_ows(value.as_bytes()))
+            .map(ContentLength)
+            .map_err(|_| HeaderError::invalid(CONTENT_LENGTH))
+    }
+
+    fn encode(&self) -> HeaderValue {
+        self.0.into()
+    }
+}
+
+/// The `Content-Type` header, the media type of the message body.
+///
+/// The media type is kept as a header value, such as
+/// `text/html; charset=utf-8`. Construction and decoding only check that it
+/// has the `type/subtype` shape, so encoding cannot fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType(HeaderValue);
+
+impl ContentType {
+    /// Creates a `Content-Type` from a media type string.
+    ///
+    /// An error is returned if `s` is not a valid header value or does not
+    /// have the `type/subtype` shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::header::ContentType;
+    ///
+    /// let content_type = ContentType::new("text/plain").unwrap();
+    /// assert_eq!(content_type.as_str(), "text/plain");
+    ///
+    /// assert!(ContentType::new("text").is_err());
+    /// assert!(ContentType::new("text/plain\nx: y").is_err());
+    /// ```
+    pub fn new(s: &str) -> Result<ContentType, HeaderError> {
+        let value = HeaderValue::from_str(s)
+            .map_err(|_| HeaderError::invalid(CONTENT_TYPE))?;
+
+        ContentType::decode(&value)
+    }
+
+    /// Returns the media type as a string.
+    pub fn as_str(&self) -> &str {
+        self.0.to_str().expect("ContentType is validated as visible ASCII")
+    }
+}
+
+impl Header for ContentType {
+    fn name() -> HeaderName {
+        CONTENT_TYPE
+    }
+
+    fn decode(value: &HeaderValue) -> Result<ContentType, HeaderError> {
+        let invalid = || HeaderError::invalid(CONTENT_TYPE);
+
+        let value = value.trim();
+        let s = value.to_str().map_err(|_| invalid())?;
+        let essence = s.split(';').next().unwrap_or("").trim();
+
+        let mut parts = essence.splitn(2, '/');
+        let type_ = parts.next().unwrap_or("");
+        let subtype = parts.next().unwrap_or("");
+
+        if type_.is_empty() || subtype.is_empty() || subtype.contains('/') {
+            return Err(invalid());
+        }
+
+        Ok(ContentType(value))
+    }
+
+    fn encode(&self) -> HeaderValue {
+        self.0.clone()
+    }
+}
@@ -1,12 +1,17 @@
 use bytes::{Bytes, BytesMut};
 
 use std::{cmp, fmt, mem, str};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::error::Error;
 use std::str::FromStr;
 
 use ::convert::HttpTryFrom;
 use ::error::Never;
+use header::list::{self, SplitQuoted};
 use header::name::HeaderName;
+use header::qvalue::QValue;
+use header::range::{self, ByteRange, ContentRange};
 
 /// Represents an HTTP header field value.
 ///
@@ -35,6 +40,56 @@ pub struct InvalidHeaderValue {
 #[derive(Debug)]
 pub struct InvalidHeaderValueBytes(InvalidHeaderValue);
 
+/// A helper for displaying a `HeaderValue` escaped for HTML contexts.
+///
+/// This struct is created by the `display_html_escaped` method on
+/// `HeaderValue`.
+pub struct HtmlEscaped<'a> {
+    value: &'a HeaderValue,
+}
+
+/// An iterator over the elements of a comma-separated `HeaderValue`.
+///
+/// This struct is created by the `split_commas` method on `HeaderValue`.
+#[derive(Debug, Clone)]
+pub struct SplitCommas<'a> {
+    rem: Option<&'a [u8]>,
+}
+
+/// An iterator over the elements of a comma-separated `HeaderValue`, which
+/// does not split on commas within quoted-strings.
+///
+/// This struct is created by the `split_comma_quoted` method on
+/// `HeaderValue`.
+#[derive(Debug, Clone)]
+pub struct SplitCommaQuoted<'a> {
+    inner: SplitQuoted<'a>,
+}
+
+/// The result of parsing a `Vary` header value with `HeaderValue::parse_vary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VaryResult {
+    /// The value is `*`: the response varies on more than request headers,
+    /// so it cannot be reused without revalidation.
+    Wildcard,
+    /// The request header fields that the response varies on.
+    Names(Vec<HeaderName>),
+}
+
+/// The credentials following the scheme of an `Authorization` or
+/// `Proxy-Authorization` header, as parsed by `HeaderValue::parse_auth`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthCredentials<'a> {
+    /// No credentials follow the scheme.
+    Empty,
+    /// A `token68` blob, such as a bearer token or the base64 encoded
+    /// credentials of the `Basic` scheme.
+    Token68(&'a str),
+    /// A list of `name=value` parameters. Names are lowercased and quoted
+    /// values are unquoted.
+    Params(Vec<(String, String)>),
+}
+
 /// A possible error when converting a `HeaderValue` to a string representation.
 ///
 /// Header field values may contain opaque bytes, in which case it is not
@@ -44,6 +99,15 @@ pub struct ToStrError {
     _priv: (),
 }
 
+/// An error when parsing a `HeaderValue` with `HeaderValue::parse`.
+#[derive(Debug)]
+pub enum ParseError<E> {
+    /// The value contains bytes that are not visible ASCII.
+    NotAscii(ToStrError),
+    /// The value is ASCII, but could not be parsed into the target type.
+    Invalid(E),
+}
+
 impl HeaderValue {
     /// Convert a static string to a `HeaderValue`.
     ///
@@ -78,6 +142,37 @@ impl HeaderValue {
         }
     }
 
+    /// Convert a static byte slice to a `HeaderValue`.
+    ///
+    /// Like `from_static`, this function does not copy. Unlike it, opaque
+    /// bytes (128-255) are permitted, as they are by `from_bytes`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument contains invalid header value
+    /// bytes, that is control characters other than tab, or DEL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static_bytes(b"hello\xfa");
+    /// assert_eq!(val, &b"hello\xfa"[..]);
+    /// ```
+    #[inline]
+    pub fn from_static_bytes(src: &'static [u8]) -> HeaderValue {
+        for &b in src {
+            if !is_valid(b) {
+                panic!("invalid header value");
+            }
+        }
+
+        HeaderValue {
+            inner: Bytes::from_static(src),
+            is_sensitive: false,
+        }
+    }
+
     /// Attempt to convert a string to a `HeaderValue`.
     ///
     /// If the argument contains invalid header value characters, an error is
@@ -154,6 +249,152 @@ impl HeaderValue {
         HeaderValue::try_from(src)
     }
 
+    /// Attempt to convert a byte slice containing obsolete line folding to a
+    /// `HeaderValue`.
+    ///
+    /// Each `CRLF` followed by one or more `SP` or `HTAB` (an `obs-fold`) is
+    /// replaced with a single `SP`, and the result is then validated as by
+    /// `from_bytes`. Any other CR or LF is rejected.
+    ///
+    /// Line folding is deprecated by [RFC 7230, Section 3.2.4], and messages
+    /// containing it should normally be rejected. This is an opt-in leniency
+    /// for servers that need to accept requests from historic clients.
+    ///
+    /// [RFC 7230, Section 3.2.4]: https://tools.ietf.org/html/rfc7230#section-3.2.4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_obs_folded(b"text/plain;\r\n\tcharset=utf-8").unwrap();
+    /// assert_eq!(val, "text/plain; charset=utf-8");
+    ///
+    /// assert!(HeaderValue::from_obs_folded(b"a\rb").is_err());
+    /// ```
+    pub fn from_obs_folded(src: &[u8]) -> Result<HeaderValue, InvalidHeaderValue> {
+        let mut buf = BytesMut::with_capacity(src.len());
+        let mut i = 0;
+
+        while i < src.len() {
+            let folded = src[i] == b'\r' &&
+                src.get(i + 1) == Some(&b'\n') &&
+                src.get(i + 2).map(|&b| b == b' ' || b == b'\t').unwrap_or(false);
+
+            if folded {
+                i += 2;
+                while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+                    i += 1;
+                }
+                buf.extend_from_slice(b" ");
+            } else {
+                buf.extend_from_slice(&src[i..i + 1]);
+                i += 1;
+            }
+        }
+
+        HeaderValue::try_from(buf)
+    }
+
+    /// Attempt to convert a byte slice to a `HeaderValue` for the header
+    /// `name`, enforcing a maximum HTTP/1.x line length.
+    ///
+    /// The line is measured as it would be written on the wire: the name,
+    /// `": "`, the value and the trailing CRLF. An error is returned if that
+    /// exceeds `max_line` bytes, or if the value is invalid as by
+    /// `from_bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderValue, HOST};
+    /// // "host: example.com\r\n" is 19 bytes.
+    /// assert!(HeaderValue::from_bytes_max_line(&HOST, b"example.com", 19).is_ok());
+    /// assert!(HeaderValue::from_bytes_max_line(&HOST, b"example.com", 18).is_err());
+    /// ```
+    pub fn from_bytes_max_line(name: &HeaderName, value: &[u8], max_line: usize)
+        -> Result<HeaderValue, InvalidHeaderValue>
+    {
+        let line = name.as_str().len()
+            .saturating_add(b": ".len())
+            .saturating_add(value.len())
+            .saturating_add(b"\r\n".len());
+
+        if line > max_line {
+            return Err(InvalidHeaderValue { _priv: () });
+        }
+
+        HeaderValue::from_bytes(value)
+    }
+
+    /// Build a `HeaderValue` by writing into a buffer.
+    ///
+    /// The closure is given an empty `Vec<u8>` to write the value into, for
+    /// example with `write!` through `std::io::Write`. The buffer grows as
+    /// needed. The bytes are validated once, as by `from_bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use std::io::Write;
+    ///
+    /// let val = HeaderValue::build_with(|buf| {
+    ///     write!(buf, "bytes={}-{}", 0, 1023).unwrap();
+    /// }).unwrap();
+    ///
+    /// assert_eq!(val, "bytes=0-1023");
+    /// ```
+    pub fn build_with<F>(f: F) -> Result<HeaderValue, InvalidHeaderValue>
+        where F: FnOnce(&mut Vec<u8>),
+    {
+        let mut buf = Vec::new();
+        f(&mut buf);
+        HeaderValue::try_from(buf)
+    }
+
+    /// Build a list `HeaderValue` from a sequence of tokens.
+    ///
+    /// Each token is validated against the `token` grammar of RFC 7230 and
+    /// the tokens are joined with `", "`. An error is returned if any token is
+    /// empty or contains a separator or whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_tokens(&["gzip", "br"]).unwrap();
+    /// assert_eq!(val, "gzip, br");
+    ///
+    /// assert!(HeaderValue::from_tokens(&["gzip, br"]).is_err());
+    /// ```
+    pub fn from_tokens<I, S>(tokens: I) -> Result<HeaderValue, InvalidHeaderValue>
+        where I: IntoIterator<Item = S>,
+              S: AsRef<[u8]>,
+    {
+        let mut buf = BytesMut::new();
+
+        for token in tokens {
+            let token = token.as_ref();
+
+            if token.is_empty() || !token.iter().all(|&b| is_tchar(b)) {
+                return Err(InvalidHeaderValue {
+                    _priv: (),
+                });
+            }
+
+            if !buf.is_empty() {
+                buf.extend_from_slice(b", ");
+            }
+
+            buf.extend_from_slice(token);
+        }
+
+        Ok(HeaderValue {
+            inner: buf.freeze(),
+            is_sensitive: false,
+        })
+    }
+
     /// Attempt to convert a `Bytes` buffer to a `HeaderValue`.
     ///
     /// If the argument contains invalid header value bytes, an error is
@@ -229,6 +470,335 @@ impl HeaderValue {
         unsafe { Ok(str::from_utf8_unchecked(bytes)) }
     }
 
+    /// Converts the value to a string, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// This mirrors `String::from_utf8_lossy`: when the value is valid UTF-8 a
+    /// borrowed `&str` is returned without copying, otherwise an owned
+    /// `String` is allocated. This is convenient for logging and diagnostics,
+    /// where `to_str` failing on opaque bytes is not helpful.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("hello");
+    /// assert_eq!(val.to_str_lossy(), "hello");
+    ///
+    /// let val = HeaderValue::from_bytes(b"hello\xfa").unwrap();
+    /// assert_eq!(val.to_str_lossy(), "hello\u{fffd}");
+    ///
+    /// let val = HeaderValue::from_static("");
+    /// assert_eq!(val.to_str_lossy(), "");
+    /// ```
+    pub fn to_str_lossy<'a>(&'a self) -> Cow<'a, str> {
+        match self.to_str() {
+            Ok(s) => Cow::Borrowed(s),
+            Err(_) => String::from_utf8_lossy(self.as_bytes()),
+        }
+    }
+
+    /// Parses the value into another type.
+    ///
+    /// The value must only contain visible ASCII, as with `to_str`, and is
+    /// then parsed with `FromStr`. The returned error distinguishes the two
+    /// failure cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("42");
+    /// assert_eq!(val.parse::<u64>().unwrap(), 42);
+    ///
+    /// let val = HeaderValue::from_static("42abc");
+    /// assert!(val.parse::<u64>().is_err());
+    /// ```
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError<T::Err>> {
+        self.to_str()
+            .map_err(ParseError::NotAscii)?
+            .parse()
+            .map_err(ParseError::Invalid)
+    }
+
+    /// Parses the value as a list of content codings, as found in the
+    /// `Content-Encoding` and `Accept-Encoding` headers.
+    ///
+    /// Each coding is returned lowercased, in order, along with its quality.
+    /// Codings without a `q` parameter, such as every coding of a
+    /// `Content-Encoding` value, have a quality of 1. Elements with a
+    /// malformed quality are skipped, and a value that is not visible ASCII
+    /// yields an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderValue, QValue};
+    /// let val = HeaderValue::from_static("br;q=0.9, GZIP;q=0.8");
+    /// let codings = val.parse_codings();
+    ///
+    /// assert_eq!(codings[0].0, "br");
+    /// assert_eq!(codings[0].1, QValue::from_thousandths(900).unwrap());
+    /// assert_eq!(codings[1].0, "gzip");
+    /// assert_eq!(codings[1].1, QValue::from_thousandths(800).unwrap());
+    /// ```
+    pub fn parse_codings(&self) -> Vec<(String, QValue)> {
+        let s = match self.to_str() {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut codings = Vec::new();
+
+        'elements: for element in s.split(',') {
+            let mut params = element.split(';');
+
+            let coding = params.next()
+                .expect("split always has at least 1 item")
+                .trim();
+
+            if coding.is_empty() {
+                continue;
+            }
+
+            let mut quality = QValue::default();
+
+            for param in params {
+                let mut kv = param.splitn(2, '=');
+                let name = kv.next().unwrap_or("").trim();
+                let value = kv.next().unwrap_or("").trim();
+
+                if name.eq_ignore_ascii_case("q") {
+                    match QValue::parse(value) {
+                        Some(q) => quality = q,
+                        None => continue 'elements,
+                    }
+                }
+            }
+
+            codings.push((coding.to_ascii_lowercase(), quality));
+        }
+
+        codings
+    }
+
+    /// Parses the value as a `Range` header in the `bytes` unit, as defined
+    /// by RFC 7233.
+    ///
+    /// Each range spec is returned in order. When `resource_len` is given,
+    /// the ranges are resolved against it: every range becomes an
+    /// `Inclusive` range within the resource, and unsatisfiable ranges are
+    /// dropped. `None` is returned if the value is malformed, or if no range
+    /// is satisfiable, in which case a server would respond with 416 (Range
+    /// Not Satisfiable).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{ByteRange, HeaderValue};
+    /// let val = HeaderValue::from_static("bytes=0-499, -500");
+    ///
+    /// assert_eq!(val.parse_byte_ranges(Some(10_000)).unwrap(), [
+    ///     ByteRange::Inclusive(0, 499),
+    ///     ByteRange::Inclusive(9500, 9999),
+    /// ]);
+    ///
+    /// assert!(val.parse_byte_ranges(Some(0)).is_none());
+    /// assert!(HeaderValue::from_static("bytes=5-4").parse_byte_ranges(None).is_none());
+    /// ```
+    pub fn parse_byte_ranges(&self, resource_len: Option<u64>) -> Option<Vec<ByteRange>> {
+        range::parse(self.as_bytes(), resource_len)
+    }
+
+    /// Parses the value as a `Content-Range` header in the `bytes` unit, as
+    /// defined by RFC 7233.
+    ///
+    /// Both the unsatisfied form, `bytes */1234`, and an unknown complete
+    /// length, as in `bytes 0-499/*`, are supported. `None` is returned if
+    /// the value is malformed, or if the range does not lie within the
+    /// complete length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("bytes 0-499/*");
+    /// let content_range = val.parse_content_range().unwrap();
+    ///
+    /// assert_eq!(content_range.range, Some((0, 499)));
+    /// assert_eq!(content_range.complete_length, None);
+    ///
+    /// let val = HeaderValue::from_static("bytes */1234");
+    /// assert_eq!(val.parse_content_range().unwrap().range, None);
+    /// ```
+    pub fn parse_content_range(&self) -> Option<ContentRange> {
+        range::parse_content_range(self.as_bytes())
+    }
+
+    /// Parses the value as a `Vary` header, as defined by RFC 7231, Section
+    /// 7.1.4.
+    ///
+    /// `VaryResult::Wildcard` is returned if any element is `*`. Otherwise,
+    /// the listed field names are returned lowercased, in order. Tokens that
+    /// are not valid header names, and repeated names, are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderValue, VaryResult, ACCEPT_ENCODING, USER_AGENT};
+    /// let val = HeaderValue::from_static("Accept-Encoding, User-Agent");
+    /// assert_eq!(val.parse_vary(), VaryResult::Names(vec![ACCEPT_ENCODING, USER_AGENT]));
+    ///
+    /// let val = HeaderValue::from_static("*");
+    /// assert_eq!(val.parse_vary(), VaryResult::Wildcard);
+    /// ```
+    pub fn parse_vary(&self) -> VaryResult {
+        let mut names = Vec::new();
+
+        for token in self.split_commas() {
+            if token == b"*" {
+                return VaryResult::Wildcard;
+            }
+
+            if let Ok(name) = HeaderName::from_bytes(token) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+
+        VaryResult::Names(names)
+    }
+
+    /// Parses the value as a `Forwarded` header, as defined by RFC 7239.
+    ///
+    /// Each comma-separated forwarded element is returned as a list of its
+    /// semicolon-separated `name=value` pairs, in order. Parameter names are
+    /// lowercased and quoted values are unquoted. Malformed pairs and empty
+    /// elements are skipped, and a value that is not visible ASCII yields an
+    /// empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("for=192.0.2.60;proto=http, for=\"[2001:db8::1]\"");
+    /// let elements = val.parse_forwarded();
+    ///
+    /// assert_eq!(elements.len(), 2);
+    /// assert_eq!(elements[0][1], ("proto".to_string(), "http".to_string()));
+    /// assert_eq!(elements[1][0], ("for".to_string(), "[2001:db8::1]".to_string()));
+    /// ```
+    pub fn parse_forwarded(&self) -> Vec<Vec<(String, String)>> {
+        let s = match self.to_str() {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut elements = Vec::new();
+
+        for element in SplitQuoted::new(s.as_bytes(), b',') {
+            let mut pairs = Vec::new();
+
+            for pair in SplitQuoted::new(element, b';') {
+                // The value was checked to be visible ASCII above
+                let pair = unsafe { str::from_utf8_unchecked(list::trim_ows(pair)) };
+                let mut kv = pair.splitn(2, '=');
+
+                let name = kv.next().unwrap_or("");
+                let value = match kv.next() {
+                    Some(value) if !name.is_empty() => value,
+                    _ => continue,
+                };
+
+                pairs.push((name.to_ascii_lowercase(), list::unquote(value).into_owned()));
+            }
+
+            if !pairs.is_empty() {
+                elements.push(pairs);
+            }
+        }
+
+        elements
+    }
+
+    /// Parses the value as the credentials of an `Authorization` or
+    /// `Proxy-Authorization` header, as defined by RFC 7235, Section 2.1.
+    ///
+    /// The auth scheme is returned as sent, and should be compared
+    /// case-insensitively. It is followed by either a `token68` blob or a
+    /// comma-separated list of parameters. `None` is returned if the value is
+    /// not visible ASCII, if the scheme is not a token, or if the parameter
+    /// list is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{AuthCredentials, HeaderValue};
+    /// let val = HeaderValue::from_static("Bearer abc.def.ghi");
+    /// let (scheme, credentials) = val.parse_auth().unwrap();
+    ///
+    /// assert_eq!(scheme, "Bearer");
+    /// assert_eq!(credentials, AuthCredentials::Token68("abc.def.ghi"));
+    ///
+    /// let val = HeaderValue::from_static("Digest username=\"Mufasa\", qop=auth");
+    /// let (scheme, credentials) = val.parse_auth().unwrap();
+    ///
+    /// assert_eq!(scheme, "Digest");
+    /// assert_eq!(credentials, AuthCredentials::Params(vec![
+    ///     ("username".to_string(), "Mufasa".to_string()),
+    ///     ("qop".to_string(), "auth".to_string()),
+    /// ]));
+    /// ```
+    pub fn parse_auth<'a>(&'a self) -> Option<(Cow<'a, str>, AuthCredentials<'a>)> {
+        let ows: &[char] = &[' ', '\t'];
+        let s = self.to_str().ok()?.trim_matches(ows);
+
+        let (scheme, rest) = match s.find(' ') {
+            Some(i) => (&s[..i], s[i + 1..].trim_matches(ows)),
+            None => (s, ""),
+        };
+
+        if scheme.is_empty() || !scheme.bytes().all(is_tchar) {
+            return None;
+        }
+
+        let credentials = if rest.is_empty() {
+            AuthCredentials::Empty
+        } else if is_token68(rest) {
+            AuthCredentials::Token68(rest)
+        } else {
+            let mut params = Vec::new();
+
+            for param in SplitQuoted::new(rest.as_bytes(), b',') {
+                // The value was checked to be visible ASCII above
+                let param = unsafe { str::from_utf8_unchecked(param) }.trim_matches(ows);
+
+                if param.is_empty() {
+                    continue;
+                }
+
+                let eq = param.find('=')?;
+                let name = param[..eq].trim_matches(ows);
+                let value = param[eq + 1..].trim_matches(ows);
+
+                if name.is_empty() || !name.bytes().all(is_tchar) || value.is_empty() {
+                    return None;
+                }
+
+                params.push((name.to_ascii_lowercase(), list::unquote(value).into_owned()));
+            }
+
+            if params.is_empty() {
+                return None;
+            }
+
+            AuthCredentials::Params(params)
+        };
+
+        Some((Cow::Borrowed(scheme), credentials))
+    }
+
     /// Returns the length of `self`.
     ///
     /// This length is in bytes.
@@ -245,35 +815,257 @@ impl HeaderValue {
         self.as_ref().len()
     }
 
-    /// Returns true if the `HeaderValue` has a length of zero bytes.
+    /// Returns true if the `HeaderValue` has a length of zero bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("");
+    /// assert!(val.is_empty());
+    ///
+    /// let val = HeaderValue::from_static("hello");
+    /// assert!(!val.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the `HeaderValue` is empty or only contains optional
+    /// whitespace (`SP` or `HTAB`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// assert!(HeaderValue::from_static("").is_blank());
+    /// assert!(HeaderValue::from_static(" \t ").is_blank());
+    /// assert!(!HeaderValue::from_static(" x ").is_blank());
+    /// ```
+    pub fn is_blank(&self) -> bool {
+        self.as_bytes().iter().all(|&b| b == b' ' || b == b'\t')
+    }
+
+    /// Returns a `HeaderValue` with leading and trailing optional whitespace
+    /// (`SP` and `HTAB`) removed.
+    ///
+    /// The returned value is a slice of the underlying buffer of `self`, so
+    /// no allocation is performed. The sensitivity flag is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("\t hello \t");
+    /// assert_eq!(val.trim(), "hello");
+    /// ```
+    pub fn trim(&self) -> HeaderValue {
+        let bytes = self.as_bytes();
+        let trimmed = list::trim_ows(bytes);
+        let start = trimmed.as_ptr() as usize - bytes.as_ptr() as usize;
+
+        HeaderValue {
+            inner: self.inner.slice(start, start + trimmed.len()),
+            is_sensitive: self.is_sensitive,
+        }
+    }
+
+    /// Appends `other` to this value as a further comma-separated list
+    /// element.
+    ///
+    /// This combines several field values of the same name into one, as
+    /// permitted by RFC 7230, Section 3.2.2. If either value is empty, the
+    /// separator is omitted. The result is sensitive if either value is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let mut val = HeaderValue::from_static("gzip");
+    /// val.append(&HeaderValue::from_static("deflate"));
+    /// assert_eq!(val, "gzip, deflate");
+    /// ```
+    pub fn append(&mut self, other: &HeaderValue) {
+        self.is_sensitive |= other.is_sensitive;
+
+        if other.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            self.inner = other.inner.clone();
+            return;
+        }
+
+        let mut buf = match mem::replace(&mut self.inner, Bytes::new()).try_mut() {
+            Ok(buf) => buf,
+            Err(bytes) => BytesMut::from(&bytes[..]),
+        };
+
+        buf.reserve(2 + other.len());
+        buf.extend_from_slice(b", ");
+        buf.extend_from_slice(other.as_bytes());
+        self.inner = buf.freeze();
+    }
+
+    /// Converts a `HeaderValue` to a byte slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("hello");
+    /// assert_eq!(val.as_bytes(), b"hello");
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    /// Compares the value to `other`, ignoring a single pair of surrounding
+    /// double quotes on `self`.
+    ///
+    /// This is useful when comparing quoted values, such as entity tags,
+    /// against an unquoted candidate. Quotes are only stripped when both the
+    /// leading and trailing quote are present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("\"abc\"");
+    /// assert!(val.eq_unquoted(b"abc"));
+    /// assert!(!val.eq_unquoted(b"\"abc"));
+    ///
+    /// let val = HeaderValue::from_static("\"abc");
+    /// assert!(!val.eq_unquoted(b"abc"));
+    /// ```
+    pub fn eq_unquoted(&self, other: &[u8]) -> bool {
+        let bytes = self.as_bytes();
+
+        let unquoted = if bytes.len() >= 2
+            && bytes[0] == b'"'
+            && bytes[bytes.len() - 1] == b'"'
+        {
+            &bytes[1..bytes.len() - 1]
+        } else {
+            bytes
+        };
+
+        unquoted == other
+    }
+
+    /// Compares the value to `other`, ignoring ASCII case.
+    ///
+    /// Only the ASCII letters `A-Z` and `a-z` are folded; all other bytes,
+    /// including opaque bytes above 127, must match exactly. This is suitable
+    /// for the many header values that are case-insensitive tokens, and does
+    /// not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("Keep-Alive");
+    /// assert!(val.eq_ignore_ascii_case(b"keep-alive"));
+    /// assert!(!val.eq_ignore_ascii_case(b"close"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &[u8]) -> bool {
+        self.as_bytes().eq_ignore_ascii_case(other)
+    }
+
+    /// Parses the value as a boolean flag.
+    ///
+    /// `true` and `1` parse as `true`, and `false` and `0` as `false`,
+    /// ignoring ASCII case. Any other value, including one with surrounding
+    /// whitespace, yields `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// assert_eq!(HeaderValue::from_static("TRUE").parse_bool(), Some(true));
+    /// assert_eq!(HeaderValue::from_static("0").parse_bool(), Some(false));
+    /// assert_eq!(HeaderValue::from_static("yes").parse_bool(), None);
+    /// ```
+    pub fn parse_bool(&self) -> Option<bool> {
+        if self.eq_ignore_ascii_case(b"true") || self == "1" {
+            Some(true)
+        } else if self.eq_ignore_ascii_case(b"false") || self == "0" {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an object that implements `Display` and renders the value
+    /// escaped for inclusion in HTML.
+    ///
+    /// The characters `<`, `>`, `&` and `"` are replaced by character
+    /// references, and bytes that are not visible ASCII are shown as `\xNN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("<script>alert(\"&\")</script>");
+    ///
+    /// assert_eq!(
+    ///     val.display_html_escaped().to_string(),
+    ///     "&lt;script&gt;alert(&quot;&amp;&quot;)&lt;/script&gt;");
+    /// ```
+    #[inline]
+    pub fn display_html_escaped<'a>(&'a self) -> HtmlEscaped<'a> {
+        HtmlEscaped { value: self }
+    }
+
+    /// Returns an iterator over the elements of a comma-separated list value,
+    /// such as `Accept-Encoding` or `Vary`.
+    ///
+    /// Optional whitespace around each element is stripped, and empty
+    /// elements, as produced by `"a,,b"` or a trailing comma, are skipped.
+    /// The elements borrow from the value, so no allocation is performed.
+    ///
+    /// Commas are not interpreted in any way: a comma inside a quoted-string
+    /// still splits the value. Use `split_comma_quoted` for values that may
+    /// contain quoted-strings.
     ///
     /// # Examples
     ///
     /// ```
     /// # use http::header::HeaderValue;
-    /// let val = HeaderValue::from_static("");
-    /// assert!(val.is_empty());
+    /// let val = HeaderValue::from_static("gzip, deflate,, br ");
+    /// let codings: Vec<_> = val.split_commas().collect();
     ///
-    /// let val = HeaderValue::from_static("hello");
-    /// assert!(!val.is_empty());
+    /// assert_eq!(codings, [&b"gzip"[..], b"deflate", b"br"]);
     /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn split_commas<'a>(&'a self) -> SplitCommas<'a> {
+        SplitCommas { rem: Some(self.as_bytes()) }
     }
 
-    /// Converts a `HeaderValue` to a byte slice.
+    /// Returns an iterator over the elements of a comma-separated list value,
+    /// skipping commas that appear within quoted-strings.
+    ///
+    /// This is like `split_commas`, but suits values such as
+    /// `WWW-Authenticate`, whose parameters may be quoted-strings containing
+    /// commas. A backslash within a quoted-string escapes the following
+    /// byte, so `\"` does not end the quoted-string. Quotes are left in
+    /// place in the returned elements.
     ///
     /// # Examples
     ///
     /// ```
     /// # use http::header::HeaderValue;
-    /// let val = HeaderValue::from_static("hello");
-    /// assert_eq!(val.as_bytes(), b"hello");
+    /// let val = HeaderValue::from_static(r#"a="x, y", b, c="\",""#);
+    /// let elements: Vec<_> = val.split_comma_quoted().collect();
+    ///
+    /// assert_eq!(elements, [&br#"a="x, y""#[..], b"b", br#"c="\",""#]);
     /// ```
     #[inline]
-    pub fn as_bytes(&self) -> &[u8] {
-        self.as_ref()
+    pub fn split_comma_quoted<'a>(&'a self) -> SplitCommaQuoted<'a> {
+        SplitCommaQuoted { inner: SplitQuoted::new(self.as_bytes(), b',') }
     }
 
     /// Mark that the header value represents sensitive information.
@@ -320,6 +1112,35 @@ impl HeaderValue {
     pub fn is_sensitive(&self) -> bool {
         self.is_sensitive
     }
+
+    /// Makes this value share the storage of an equal value from `seen`, or
+    /// adds its storage to `seen` if there is none.
+    pub(super) fn share_storage(&mut self, seen: &mut HashSet<Bytes>) {
+        if let Some(shared) = seen.get(&self.inner) {
+            self.inner = shared.clone();
+            return;
+        }
+
+        seen.insert(self.inner.clone());
+    }
+
+    /// Returns a string of `*` characters with the same length as the value.
+    ///
+    /// The `Debug` implementation hides sensitive values behind a fixed
+    /// string. This is an alternative for logs where the length of the value
+    /// is still useful, for example to spot truncated tokens, while its
+    /// content must remain hidden. The length is counted in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("Bearer abc123");
+    /// assert_eq!(val.redacted_preserving_len(), "*************");
+    /// ```
+    pub fn redacted_preserving_len(&self) -> String {
+        "*".repeat(self.len())
+    }
 }
 
 impl AsRef<[u8]> for HeaderValue {
@@ -361,6 +1182,91 @@ impl fmt::Debug for HeaderValue {
     }
 }
 
+impl<'a> fmt::Display for HtmlEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut from = 0;
+        let bytes = self.value.as_bytes();
+
+        for (i, &b) in bytes.iter().enumerate() {
+            let escaped = match b {
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'&' => "&amp;",
+                b'"' => "&quot;",
+                b if b >= 32 && b < 127 => continue,
+                _ => "",
+            };
+
+            if from != i {
+                f.write_str(unsafe {
+                    str::from_utf8_unchecked(&bytes[from..i])
+                })?;
+            }
+
+            if escaped.is_empty() {
+                write!(f, "\\x{:02x}", b)?;
+            } else {
+                f.write_str(escaped)?;
+            }
+
+            from = i + 1;
+        }
+
+        f.write_str(unsafe {
+            str::from_utf8_unchecked(&bytes[from..])
+        })
+    }
+}
+
+impl<'a> fmt::Debug for HtmlEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<'a> Iterator for SplitCommas<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        while let Some(rem) = self.rem {
+            let element = match rem.iter().position(|&b| b == b',') {
+                Some(i) => {
+                    self.rem = Some(&rem[i + 1..]);
+                    &rem[..i]
+                }
+                None => {
+                    self.rem = None;
+                    rem
+                }
+            };
+
+            let element = list::trim_ows(element);
+
+            if !element.is_empty() {
+                return Some(element);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> Iterator for SplitCommaQuoted<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        while let Some(element) = self.inner.next() {
+            let element = list::trim_ows(element);
+
+            if !element.is_empty() {
+                return Some(element);
+            }
+        }
+
+        None
+    }
+}
+
 impl From<HeaderName> for HeaderValue {
     #[inline]
     fn from(h: HeaderName) -> HeaderValue {
@@ -456,6 +1362,61 @@ from_integers! {
     from_isize: isize => 20
 }
 
+/// Converts a boolean into the value `true` or `false`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValue;
+/// assert_eq!(HeaderValue::from(true), "true");
+/// assert_eq!(HeaderValue::from(false), "false");
+/// ```
+impl From<bool> for HeaderValue {
+    #[inline]
+    fn from(b: bool) -> HeaderValue {
+        if b {
+            HeaderValue::from_static("true")
+        } else {
+            HeaderValue::from_static("false")
+        }
+    }
+}
+
+impl HttpTryFrom<bool> for HeaderValue {
+    type Error = Never;
+
+    #[inline]
+    fn try_from(b: bool) -> Result<Self, Self::Error> {
+        Ok(b.into())
+    }
+}
+
+macro_rules! try_from_floats {
+    ($($t:ident),*) => {$(
+        /// Formats a finite number in plain decimal notation, without an
+        /// exponent.
+        ///
+        /// NaN and infinite values are not valid header text, so they are
+        /// rejected.
+        impl HttpTryFrom<$t> for HeaderValue {
+            type Error = InvalidHeaderValue;
+
+            fn try_from(num: $t) -> Result<Self, Self::Error> {
+                if !num.is_finite() {
+                    return Err(InvalidHeaderValue { _priv: () });
+                }
+
+                Ok(HeaderValue {
+                    inner: num.to_string().into(),
+                    is_sensitive: false,
+                })
+            }
+        }
+    )*};
+}
+
+try_from_floats!(f32, f64);
+
 #[cfg(test)]
 mod from_header_name_tests {
     use super::*;
@@ -581,6 +1542,25 @@ fn is_valid(b: u8) -> bool {
     b >= 32 && b != 127 || b == b'\t'
 }
 
+/// Returns true if `s` is a `token68`, as defined by RFC 7235, Section 2.1.
+fn is_token68(s: &str) -> bool {
+    let padding = s.bytes().rev().take_while(|&b| b == b'=').count();
+    let body = &s.as_bytes()[..s.len() - padding];
+
+    !body.is_empty() && body.iter().all(|&b| match b {
+        b'-' | b'.' | b'_' | b'~' | b'+' | b'/' => true,
+        _ => (b as char).is_ascii_alphanumeric(),
+    })
+}
+
+fn is_tchar(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' |
+        b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => (b as char).is_ascii_alphanumeric(),
+    }
+}
+
 impl fmt::Display for InvalidHeaderValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.description().fmt(f)
@@ -617,6 +1597,24 @@ impl Error for ToStrError {
     }
 }
 
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::NotAscii(ref e) => e.fmt(f),
+            ParseError::Invalid(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: Error> Error for ParseError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::NotAscii(_) => "failed to convert header to a str",
+            ParseError::Invalid(_) => "failed to parse header value",
+        }
+    }
+}
+
 // ===== PartialEq / PartialOrd =====
 
 impl PartialEq for HeaderValue {
@@ -772,6 +1770,48 @@ impl<'a> PartialOrd<HeaderValue> for &'a str {
     }
 }
 
+impl<const N: usize> PartialEq<[u8; N]> for HeaderValue {
+    #[inline]
+    fn eq(&self, other: &[u8; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<const N: usize> PartialOrd<[u8; N]> for HeaderValue {
+    #[inline]
+    fn partial_cmp(&self, other: &[u8; N]) -> Option<cmp::Ordering> {
+        self.partial_cmp(&other[..])
+    }
+}
+
+impl<const N: usize> PartialEq<HeaderValue> for [u8; N] {
+    #[inline]
+    fn eq(&self, other: &HeaderValue) -> bool {
+        *other == *self
+    }
+}
+
+impl<const N: usize> PartialOrd<HeaderValue> for [u8; N] {
+    #[inline]
+    fn partial_cmp(&self, other: &HeaderValue) -> Option<cmp::Ordering> {
+        self[..].partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a, const N: usize> PartialEq<HeaderValue> for &'a [u8; N] {
+    #[inline]
+    fn eq(&self, other: &HeaderValue) -> bool {
+        *other == **self
+    }
+}
+
+impl<'a, const N: usize> PartialOrd<HeaderValue> for &'a [u8; N] {
+    #[inline]
+    fn partial_cmp(&self, other: &HeaderValue) -> Option<cmp::Ordering> {
+        self[..].partial_cmp(other.as_bytes())
+    }
+}
+
 #[test]
 fn test_try_from() {
     HeaderValue::try_from(vec![127]).unwrap_err();
@@ -795,3 +1835,484 @@ fn test_debug() {
     sensitive.set_sensitive(true);
     assert_eq!("Sensitive", format!("{:?}", sensitive));
 }
+
+#[test]
+fn test_eq_unquoted() {
+    assert!(HeaderValue::from_static("\"abc\"").eq_unquoted(b"abc"));
+    assert!(HeaderValue::from_static("abc").eq_unquoted(b"abc"));
+    assert!(HeaderValue::from_static("\"\"").eq_unquoted(b""));
+    assert!(HeaderValue::from_static("\"\"abc\"\"").eq_unquoted(b"\"abc\""));
+
+    assert!(!HeaderValue::from_static("\"abc").eq_unquoted(b"abc"));
+    assert!(!HeaderValue::from_static("abc\"").eq_unquoted(b"abc"));
+    assert!(!HeaderValue::from_static("\"").eq_unquoted(b""));
+    assert!(!HeaderValue::from_static("\"abc\"").eq_unquoted(b"\"abc\""));
+}
+
+#[test]
+fn test_is_blank() {
+    assert!(HeaderValue::from_static("").is_blank());
+    assert!(HeaderValue::from_static("  ").is_blank());
+    assert!(HeaderValue::from_static("\t").is_blank());
+    assert!(!HeaderValue::from_static("x").is_blank());
+    assert!(!HeaderValue::from_static(" x\t").is_blank());
+}
+
+#[test]
+fn test_from_tokens() {
+    let val = HeaderValue::from_tokens(vec!["gzip", "deflate", "br"]).unwrap();
+    assert_eq!(val, "gzip, deflate, br");
+
+    let val = HeaderValue::from_tokens(vec![String::from("chunked")]).unwrap();
+    assert_eq!(val, "chunked");
+
+    let val = HeaderValue::from_tokens(Vec::<&str>::new()).unwrap();
+    assert!(val.is_empty());
+
+    assert!(HeaderValue::from_tokens(vec!["gzip", "de,flate"]).is_err());
+    assert!(HeaderValue::from_tokens(vec!["gzip", ""]).is_err());
+    assert!(HeaderValue::from_tokens(vec!["a b"]).is_err());
+    assert!(HeaderValue::from_tokens(vec![&b"\xfa"[..]]).is_err());
+}
+
+#[test]
+fn test_parse_codings() {
+    let q = |v| QValue::from_thousandths(v).unwrap();
+
+    let val = HeaderValue::from_static("gzip, deflate");
+    assert_eq!(val.parse_codings(), vec![
+        ("gzip".to_string(), q(1000)),
+        ("deflate".to_string(), q(1000)),
+    ]);
+
+    let val = HeaderValue::from_static("br;q=0.9, gzip;q=0.8");
+    assert_eq!(val.parse_codings(), vec![
+        ("br".to_string(), q(900)),
+        ("gzip".to_string(), q(800)),
+    ]);
+
+    let val = HeaderValue::from_static("Identity ; Q=0 ,, *;q=0.5, x;q=2");
+    assert_eq!(val.parse_codings(), vec![
+        ("identity".to_string(), q(0)),
+        ("*".to_string(), q(500)),
+    ]);
+
+    assert!(HeaderValue::from_static("").parse_codings().is_empty());
+}
+
+#[test]
+fn test_parse_forwarded() {
+    let pairs = |v: &[(&str, &str)]| -> Vec<(String, String)> {
+        v.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect()
+    };
+
+    let val = HeaderValue::from_static("for=192.0.2.60;proto=http;by=203.0.113.43");
+    assert_eq!(val.parse_forwarded(), vec![
+        pairs(&[("for", "192.0.2.60"), ("proto", "http"), ("by", "203.0.113.43")]),
+    ]);
+
+    let val = HeaderValue::from_static("For=\"[2001:db8:cafe::17]:4711\", for=192.0.2.43 ;Host=\"a,b\"");
+    assert_eq!(val.parse_forwarded(), vec![
+        pairs(&[("for", "[2001:db8:cafe::17]:4711")]),
+        pairs(&[("for", "192.0.2.43"), ("host", "a,b")]),
+    ]);
+
+    let val = HeaderValue::from_static("for=a;;bogus;=x, ,");
+    assert_eq!(val.parse_forwarded(), vec![pairs(&[("for", "a")])]);
+
+    assert!(HeaderValue::from_static("").parse_forwarded().is_empty());
+}
+
+#[test]
+fn test_display_html_escaped() {
+    let val = HeaderValue::from_static("<script>alert('x')</script>");
+    assert_eq!(val.display_html_escaped().to_string(),
+               "&lt;script&gt;alert('x')&lt;/script&gt;");
+
+    let val = HeaderValue::from_bytes(b"a\tb\xfa\"&").unwrap();
+    assert_eq!(val.display_html_escaped().to_string(), "a\\x09b\\xfa&quot;&amp;");
+
+    let val = HeaderValue::from_static("plain");
+    assert_eq!(val.display_html_escaped().to_string(), "plain");
+}
+
+#[test]
+fn test_from_obs_folded() {
+    let val = HeaderValue::from_obs_folded(b"first line\r\n  second line").unwrap();
+    assert_eq!(val, "first line second line");
+
+    let val = HeaderValue::from_obs_folded(b"a\r\n \t\r\n\tb").unwrap();
+    assert_eq!(val, "a  b");
+
+    let val = HeaderValue::from_obs_folded(b"unfolded").unwrap();
+    assert_eq!(val, "unfolded");
+
+    assert!(HeaderValue::from_obs_folded(b"a\rb").is_err());
+    assert!(HeaderValue::from_obs_folded(b"a\r\nb").is_err());
+    assert!(HeaderValue::from_obs_folded(b"a\n b").is_err());
+}
+
+#[test]
+fn test_to_str_lossy() {
+    fn is_borrowed(cow: &Cow<str>) -> bool {
+        match *cow {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        }
+    }
+
+    let val = HeaderValue::from_static("text/plain");
+    let s = val.to_str_lossy();
+    assert_eq!(s, "text/plain");
+    assert!(is_borrowed(&s));
+
+    let val = HeaderValue::from_static("");
+    assert!(is_borrowed(&val.to_str_lossy()));
+
+    let val = HeaderValue::from_bytes("caf\u{e9}\tbar".as_bytes()).unwrap();
+    let s = val.to_str_lossy();
+    assert_eq!(s, "caf\u{e9}\tbar");
+    assert!(is_borrowed(&s));
+
+    let val = HeaderValue::from_bytes(b"a\xfab").unwrap();
+    let s = val.to_str_lossy();
+    assert_eq!(s, "a\u{fffd}b");
+    assert!(!is_borrowed(&s));
+}
+
+#[test]
+fn test_eq_ignore_ascii_case() {
+    let val = HeaderValue::from_static("Keep-Alive");
+
+    assert!(val.eq_ignore_ascii_case(b"keep-alive"));
+    assert!(val.eq_ignore_ascii_case(b"KEEP-ALIVE"));
+    assert!(val.eq_ignore_ascii_case(b"Keep-Alive"));
+    assert!(!val.eq_ignore_ascii_case(b"keep-alive "));
+    assert!(!val.eq_ignore_ascii_case(b"close"));
+
+    let val = HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap();
+    assert!(val.eq_ignore_ascii_case(b"CAF\xc3\xa9"));
+    assert!(!val.eq_ignore_ascii_case(b"CAF\xe3\xa9"));
+    assert!(!val.eq_ignore_ascii_case(b"caf\xc3\x89"));
+}
+
+#[test]
+fn test_eq_byte_array() {
+    let val = HeaderValue::from_static("GET");
+
+    assert!(val == *b"GET");
+    assert!(val == b"GET");
+    assert!(*b"GET" == val);
+    assert!(b"GET" == val);
+    assert!(val != b"POST");
+    assert!(val != b"");
+
+    assert!(val < *b"PUT");
+    assert!(val > b"DELETE");
+    assert!(b"HEAD" > val);
+    assert!(*b"CONNECT" < val);
+}
+
+#[test]
+fn test_split_commas() {
+    let val = HeaderValue::from_static("gzip, deflate, br");
+    let parts: Vec<_> = val.split_commas().collect();
+    assert_eq!(parts, [&b"gzip"[..], b"deflate", b"br"]);
+
+    let val = HeaderValue::from_static(" , a , ");
+    let parts: Vec<_> = val.split_commas().collect();
+    assert_eq!(parts, [&b"a"[..]]);
+
+    let val = HeaderValue::from_static("a,,\tb\t,");
+    let parts: Vec<_> = val.split_commas().collect();
+    assert_eq!(parts, [&b"a"[..], b"b"]);
+
+    let val = HeaderValue::from_static("gzip");
+    let parts: Vec<_> = val.split_commas().collect();
+    assert_eq!(parts, [&b"gzip"[..]]);
+
+    let val = HeaderValue::from_static("a=\"x,y\"");
+    let parts: Vec<_> = val.split_commas().collect();
+    assert_eq!(parts, [&b"a=\"x"[..], b"y\""]);
+
+    assert_eq!(HeaderValue::from_static("").split_commas().count(), 0);
+    assert_eq!(HeaderValue::from_static(" ,, ").split_commas().count(), 0);
+}
+
+#[test]
+fn test_trim() {
+    let val = HeaderValue::from_bytes(b"\t hi \t").unwrap();
+    assert_eq!(val.trim(), "hi");
+
+    // Short buffers are stored inline by `Bytes`, so use one large enough to
+    // be shared.
+    let long = format!(" {} ", "x".repeat(64));
+    let val = HeaderValue::from_shared(long.into()).unwrap();
+    let trimmed = val.trim();
+    assert_eq!(trimmed.len(), 64);
+    assert_eq!(trimmed.as_bytes().as_ptr(), val.as_bytes()[1..].as_ptr());
+
+    let mut val = HeaderValue::from_static("secret ");
+    val.set_sensitive(true);
+    let trimmed = val.trim();
+    assert_eq!(trimmed, "secret");
+    assert!(trimmed.is_sensitive());
+
+    let val = HeaderValue::from_static(" \t ");
+    assert!(val.trim().is_empty());
+
+    let val = HeaderValue::from_static("");
+    assert!(val.trim().is_empty());
+
+    let val = HeaderValue::from_static("a b");
+    assert_eq!(val.trim(), "a b");
+}
+
+#[test]
+fn test_parse() {
+    let val = HeaderValue::from_static("42");
+    assert_eq!(val.parse::<u64>().unwrap(), 42);
+    assert_eq!(val.parse::<String>().unwrap(), "42");
+
+    let val = HeaderValue::from_static("42abc");
+    match val.parse::<u64>() {
+        Err(ParseError::Invalid(_)) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let val = HeaderValue::from_bytes(b"4\xfa2").unwrap();
+    match val.parse::<u64>() {
+        Err(ParseError::NotAscii(_)) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let val = HeaderValue::from_static("-1");
+    assert!(val.parse::<u64>().is_err());
+    assert_eq!(val.parse::<i64>().unwrap(), -1);
+}
+
+#[test]
+fn test_from_static_bytes() {
+    let val = HeaderValue::from_static_bytes(b"hello\xfa");
+    assert_eq!(val, &b"hello\xfa"[..]);
+    assert!(val.to_str().is_err());
+
+    let val = HeaderValue::from_static_bytes(b"a\tb");
+    assert_eq!(val, "a\tb");
+}
+
+#[test]
+#[should_panic]
+fn test_from_static_bytes_newline() {
+    HeaderValue::from_static_bytes(b"\n");
+}
+
+#[test]
+#[should_panic]
+fn test_from_static_bytes_del() {
+    HeaderValue::from_static_bytes(b"a\x7f");
+}
+
+#[test]
+fn test_redacted_preserving_len() {
+    let val = HeaderValue::from_static("my secret");
+    let redacted = val.redacted_preserving_len();
+    assert_eq!(redacted.len(), val.len());
+    assert!(redacted.chars().all(|c| c == '*'));
+
+    let val = HeaderValue::from_bytes(b"\xfa\xfb").unwrap();
+    assert_eq!(val.redacted_preserving_len(), "**");
+
+    assert_eq!(HeaderValue::from_static("").redacted_preserving_len(), "");
+}
+
+#[test]
+fn test_build_with() {
+    use std::io::Write;
+
+    let val = HeaderValue::build_with(|buf| {
+        buf.extend_from_slice(b"bytes=");
+        ::itoa::write(&mut *buf, 0).unwrap();
+        write!(buf, "-{}/{}", 1023, 4096).unwrap();
+    }).unwrap();
+    assert_eq!(val, "bytes=0-1023/4096");
+
+    // The buffer grows past any inline capacity.
+    let long = "x".repeat(100);
+    let val = HeaderValue::build_with(|buf| {
+        write!(buf, "{}; {}", long, long).unwrap();
+    }).unwrap();
+    assert_eq!(val.len(), 202);
+    assert_eq!(val, &format!("{}; {}", long, long));
+
+    let val = HeaderValue::build_with(|_| {}).unwrap();
+    assert!(val.is_empty());
+
+    assert!(HeaderValue::build_with(|buf| {
+        write!(buf, "a\nb").unwrap();
+    }).is_err());
+}
+
+#[test]
+fn test_from_bytes_max_line() {
+    use header::CONTENT_TYPE;
+
+    // "content-type: " is 14 bytes, CRLF is 2.
+    let value = [b'a'; 84];
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, &value, 100).is_ok());
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, &value, 99).is_err());
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, &value[..83], 99).is_ok());
+
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, b"", 16).is_ok());
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, b"", 15).is_err());
+    assert!(HeaderValue::from_bytes_max_line(&CONTENT_TYPE, b"a\nb", 100).is_err());
+}
+
+#[test]
+fn test_bool() {
+    for &b in &[true, false] {
+        let val = HeaderValue::from(b);
+        assert_eq!(val.parse_bool(), Some(b));
+        assert_eq!(val.to_str().unwrap(), b.to_string());
+    }
+
+    let parse = |s| HeaderValue::from_static(s).parse_bool();
+
+    assert_eq!(parse("TRUE"), Some(true));
+    assert_eq!(parse("True"), Some(true));
+    assert_eq!(parse("1"), Some(true));
+    assert_eq!(parse("fAlSe"), Some(false));
+    assert_eq!(parse("0"), Some(false));
+
+    assert_eq!(parse(""), None);
+    assert_eq!(parse(" true"), None);
+    assert_eq!(parse("01"), None);
+    assert_eq!(parse("yes"), None);
+}
+
+#[test]
+fn test_parse_vary() {
+    use header::{ACCEPT_ENCODING, USER_AGENT};
+
+    fn vary(s: &'static str) -> VaryResult {
+        HeaderValue::from_static(s).parse_vary()
+    }
+
+    assert_eq!(vary("*"), VaryResult::Wildcard);
+    assert_eq!(vary("accept, *"), VaryResult::Wildcard);
+
+    assert_eq!(vary("Accept-Encoding, User-Agent"),
+               VaryResult::Names(vec![ACCEPT_ENCODING, USER_AGENT]));
+    assert_eq!(vary("accept-encoding,,ACCEPT-ENCODING, bad token, x-custom"),
+               VaryResult::Names(vec![ACCEPT_ENCODING, HeaderName::from_static("x-custom")]));
+
+    assert_eq!(vary(""), VaryResult::Names(Vec::new()));
+    assert_eq!(vary(" , "), VaryResult::Names(Vec::new()));
+}
+
+#[test]
+fn test_parse_auth() {
+    fn check(s: &'static str, scheme: &str, credentials: AuthCredentials) {
+        let val = HeaderValue::from_static(s);
+        let (actual_scheme, actual_credentials) = val.parse_auth().unwrap();
+
+        assert_eq!(actual_scheme, scheme, "{:?}", s);
+        assert_eq!(actual_credentials, credentials, "{:?}", s);
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> AuthCredentials<'static> {
+        AuthCredentials::Params(pairs.iter()
+            .map(|&(k, v)| (k.to_string(), v.to_string()))
+            .collect())
+    }
+
+    check("Bearer abc.def.ghi", "Bearer", AuthCredentials::Token68("abc.def.ghi"));
+    check("Basic dXNlcjpwYXNz", "Basic", AuthCredentials::Token68("dXNlcjpwYXNz"));
+    check(" basic  dXNlcjo= ", "basic", AuthCredentials::Token68("dXNlcjo="));
+    check("Negotiate", "Negotiate", AuthCredentials::Empty);
+    check("Digest Realm=\"a, b\", nonce = \"x\\\"y\", qop=auth,",
+          "Digest",
+          params(&[("realm", "a, b"), ("nonce", "x\"y"), ("qop", "auth")]));
+
+    let invalid = [
+        "",
+        "Bad/Scheme abc",
+        "Digest realm, qop=auth",
+        "Digest realm=, qop=auth",
+        "Digest =a",
+    ];
+
+    for s in invalid.iter() {
+        assert!(HeaderValue::from_static(s).parse_auth().is_none(), "{:?}", s);
+    }
+}
+
+#[test]
+fn test_append() {
+    let mut val = HeaderValue::from_static("gzip");
+    val.append(&HeaderValue::from_static("deflate"));
+    assert_eq!(val, "gzip, deflate");
+    assert!(!val.is_sensitive());
+
+    let mut val = HeaderValue::from_str("a").unwrap();
+    val.append(&HeaderValue::from_static("b"));
+    val.append(&HeaderValue::from_static("c"));
+    assert_eq!(val, "a, b, c");
+
+    let mut val = HeaderValue::from_static("");
+    val.append(&HeaderValue::from_static("x"));
+    val.append(&HeaderValue::from_static(""));
+    assert_eq!(val, "x");
+
+    let mut secret = HeaderValue::from_static("token");
+    secret.set_sensitive(true);
+
+    let mut val = HeaderValue::from_static("basic");
+    val.append(&secret);
+    assert!(val.is_sensitive());
+
+    let mut val = secret.clone();
+    val.append(&HeaderValue::from_static("basic"));
+    assert!(val.is_sensitive());
+    assert_eq!(val, "token, basic");
+    assert_eq!(secret, "token");
+}
+
+#[test]
+fn test_split_comma_quoted() {
+    fn check(s: &'static str, expected: &[&str]) {
+        let val = HeaderValue::from_static(s);
+        let parts: Vec<_> = val.split_comma_quoted()
+            .map(|b| str::from_utf8(b).unwrap())
+            .collect();
+        assert_eq!(parts, expected, "{:?}", s);
+    }
+
+    check("gzip, deflate,, br ", &["gzip", "deflate", "br"]);
+    check("", &[]);
+    check(r#"Basic realm="a, b", Bearer"#, &[r#"Basic realm="a, b""#, "Bearer"]);
+    check(r#"x="a\", b", y"#, &[r#"x="a\", b""#, "y"]);
+    check(r#"x="a\\", b"#, &[r#"x="a\\""#, "b"]);
+    check(r#"x="unterminated, y"#, &[r#"x="unterminated, y"#]);
+}
+
+#[test]
+fn test_try_from_float() {
+    fn try_from<T>(num: T) -> Result<HeaderValue, InvalidHeaderValue>
+        where HeaderValue: HttpTryFrom<T, Error = InvalidHeaderValue>,
+    {
+        HttpTryFrom::try_from(num)
+    }
+
+    assert_eq!(try_from(0.5f32).unwrap(), "0.5");
+    assert_eq!(try_from(0.5f64).unwrap(), "0.5");
+    assert_eq!(try_from(1.0f64).unwrap(), "1");
+    assert_eq!(try_from(-2.25f64).unwrap(), "-2.25");
+    assert_eq!(try_from(1e21f64).unwrap(), "1000000000000000000000");
+    assert_eq!(try_from(1e-7f64).unwrap(), "0.0000001");
+
+    assert!(try_from(::std::f32::NAN).is_err());
+    assert!(try_from(::std::f64::NAN).is_err());
+    assert!(try_from(::std::f64::INFINITY).is_err());
+    assert!(try_from(::std::f64::NEG_INFINITY).is_err());
+}
@@ -0,0 +1,66 @@
+//! `Serialize` and `Deserialize` for `HeaderValue`, enabled by the `serde`
+//! feature.
+//!
+//! A value is serialized as a string when it only contains visible ASCII, and
+//! as a sequence of bytes otherwise. Either form is accepted when
+//! deserializing, and validated as by `HeaderValue::from_str` and
+//! `HeaderValue::from_bytes` respectively.
+//!
+//! Deserialization asks for bytes rather than for any type, so that formats
+//! which are not self-describing, such as bincode, can decode either form.
+//! Self-describing formats still pass strings through as they are.
+//!
+//! The sensitivity flag is not serialized; deserialized values are never
+//! marked as sensitive.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::HeaderValue;
+
+impl Serialize for HeaderValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_str() {
+            Ok(s) => serializer.serialize_str(s),
+            Err(_) => serializer.serialize_bytes(self.as_bytes()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<HeaderValue, D::Error> {
+        deserializer.deserialize_bytes(HeaderValueVisitor)
+    }
+}
+
+struct HeaderValueVisitor;
+
+impl<'de> Visitor<'de> for HeaderValueVisitor {
+    type Value = HeaderValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a header value string or byte sequence")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<HeaderValue, E> {
+        HeaderValue::from_str(v)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<HeaderValue, E> {
+        HeaderValue::from_bytes(v)
+            .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<HeaderValue, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+
+        self.visit_bytes(&bytes)
+    }
+}
@@ -162,6 +162,8 @@
 extern crate bytes;
 extern crate fnv;
 extern crate itoa;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 pub mod header;
 pub mod method;
@@ -19,9 +19,12 @@ use HttpTryFrom;
 use self::Inner::*;
 
 use std::{fmt, str};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::AsRef;
 use std::error::Error;
 use std::str::FromStr;
+use std::sync::{Once, RwLock};
 
 /// The Request Method (VERB)
 ///
@@ -63,12 +66,33 @@ enum Inner {
     Patch,
     // If the extension is short enough, store it inline
     ExtensionInline([u8; MAX_INLINE], u8),
-    // Otherwise, allocate it
-    ExtensionAllocated(Box<[u8]>),
+    // Otherwise, allocate it, unless it is borrowed from a `&'static str`
+    ExtensionAllocated(Cow<'static, [u8]>),
 }
 
 const MAX_INLINE: usize = 15;
 
+/// Semantics registered for an extension method.
+#[derive(Clone, Copy)]
+struct Semantics {
+    safe: bool,
+    idempotent: bool,
+}
+
+type Registry = RwLock<HashMap<String, Semantics>>;
+
+static REGISTRY_INIT: Once = Once::new();
+static mut REGISTRY: *const Registry = 0 as *const Registry;
+
+fn registry() -> &'static Registry {
+    unsafe {
+        REGISTRY_INIT.call_once(|| {
+            REGISTRY = Box::into_raw(Box::new(RwLock::new(HashMap::new())));
+        });
+        &*REGISTRY
+    }
+}
+
 // From the HTTP spec section 5.1.1, the HTTP method is case-sensitive and can
 // contain the following characters:
 //
@@ -188,12 +212,85 @@ impl Method {
 
                     write_checked(src, &mut data)?;
 
-                    Ok(Method(ExtensionAllocated(data.into_boxed_slice())))
+                    Ok(Method(ExtensionAllocated(Cow::Owned(data))))
                 }
             }
         }
     }
 
+    /// Converts a static string to an HTTP method.
+    ///
+    /// This function never allocates: a long extension method borrows the
+    /// string instead of copying it, as `from_bytes` would. The result is
+    /// equal to the one of `from_bytes` for the same name.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument is empty or contains characters
+    /// that are not valid in a method token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// let propfind = Method::from_static("PROPFIND");
+    /// assert_eq!(propfind, Method::from_bytes(b"PROPFIND").unwrap());
+    ///
+    /// assert_eq!(Method::from_static("GET"), Method::GET);
+    /// ```
+    pub fn from_static(src: &'static str) -> Method {
+        let bytes = src.as_bytes();
+
+        if bytes.is_empty() || bytes.iter().any(|&b| METHOD_CHARS[b as usize] == 0) {
+            panic!("invalid method");
+        }
+
+        if bytes.len() < MAX_INLINE {
+            // Standard and short methods are stored without allocation.
+            Method::from_bytes(bytes).expect("method is valid")
+        } else {
+            Method(ExtensionAllocated(Cow::Borrowed(bytes)))
+        }
+    }
+
+    /// Converts a string to one of the standard HTTP methods.
+    ///
+    /// Unlike `from_bytes`, extension methods are not accepted: `None` is
+    /// returned for anything but the exact, case-sensitive name of a method
+    /// with a constant on `Method`. This never allocates, so it suits
+    /// routers that only dispatch on the standard methods. The constants
+    /// can be used as patterns to match on the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// let route = match Method::from_str_standard("GET") {
+    ///     Some(Method::GET) | Some(Method::HEAD) => "read",
+    ///     Some(Method::POST) => "create",
+    ///     Some(_) => "other",
+    ///     None => "unsupported",
+    /// };
+    /// assert_eq!(route, "read");
+    ///
+    /// assert_eq!(Method::from_str_standard("PROPFIND"), None);
+    /// assert_eq!(Method::from_str_standard("get"), None);
+    /// ```
+    pub fn from_str_standard(src: &str) -> Option<Method> {
+        match src {
+            "OPTIONS" => Some(Method(Options)),
+            "GET" => Some(Method(Get)),
+            "POST" => Some(Method(Post)),
+            "PUT" => Some(Method(Put)),
+            "DELETE" => Some(Method(Delete)),
+            "HEAD" => Some(Method(Head)),
+            "TRACE" => Some(Method(Trace)),
+            "CONNECT" => Some(Method(Connect)),
+            "PATCH" => Some(Method(Patch)),
+            _ => None,
+        }
+    }
+
     fn extension_inline(src: &[u8]) -> Result<Method, InvalidMethod> {
         let mut data: [u8; MAX_INLINE] = Default::default();
 
@@ -210,6 +307,9 @@ impl Method {
     pub fn is_safe(&self) -> bool {
         match self.0 {
             Get | Head | Options | Trace => true,
+            ExtensionInline(..) | ExtensionAllocated(..) => {
+                self.registered_semantics().map(|s| s.safe).unwrap_or(false)
+            }
             _ => false
         }
     }
@@ -225,11 +325,113 @@ impl Method {
         } else {
             match self.0 {
                 Put | Delete => true,
+                ExtensionInline(..) | ExtensionAllocated(..) => {
+                    self.registered_semantics().map(|s| s.idempotent).unwrap_or(false)
+                }
                 _ => false
             }
         }
     }
 
+    /// Registers the semantics of an extension method, process-wide.
+    ///
+    /// Once registered, `is_safe` and `is_idempotent` report the given
+    /// values for any extension method named `name`. A method registered as
+    /// safe is always idempotent. Registering a name again replaces the
+    /// previous semantics; the semantics of the standard methods cannot be
+    /// changed.
+    ///
+    /// This is intended to be called once, during startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// Method::register_semantics("SEARCH", true, true);
+    ///
+    /// let search = Method::from_bytes(b"SEARCH").unwrap();
+    /// assert!(search.is_safe());
+    /// assert!(search.is_idempotent());
+    /// ```
+    pub fn register_semantics(name: &str, safe: bool, idempotent: bool) {
+        let semantics = Semantics {
+            safe: safe,
+            idempotent: idempotent,
+        };
+
+        registry()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), semantics);
+    }
+
+    fn registered_semantics(&self) -> Option<Semantics> {
+        registry()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(self.as_str())
+            .cloned()
+    }
+
+    /// Whether a request with this method is conventionally expected to
+    /// carry a body.
+    ///
+    /// This returns `false` for `GET`, `HEAD`, `DELETE`, `OPTIONS`, `TRACE`
+    /// and `CONNECT`, and `true` for `POST`, `PUT` and `PATCH`. Extension
+    /// methods have no defined semantics and are assumed to carry a body.
+    ///
+    /// This is advisory only: any request may carry a body, and its presence
+    /// is ultimately determined by the message framing. The one exception is
+    /// `TRACE`, which must not carry a body at all (RFC 7231, Section 4.3.8).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// assert!(Method::POST.expects_request_body());
+    /// assert!(!Method::GET.expects_request_body());
+    /// ```
+    pub fn expects_request_body(&self) -> bool {
+        match self.0 {
+            Get | Head | Delete | Options | Trace | Connect => false,
+            _ => true,
+        }
+    }
+
+    /// Returns true if this is the `OPTIONS` method.
+    ///
+    /// A server typically answers `OPTIONS *` itself, describing its own
+    /// capabilities rather than those of a particular resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// assert!(Method::OPTIONS.is_options());
+    /// assert!(!Method::GET.is_options());
+    /// ```
+    #[inline]
+    pub fn is_options(&self) -> bool {
+        self.0 == Options
+    }
+
+    /// Returns true if this is the `TRACE` method.
+    ///
+    /// A `TRACE` request must not carry a body, and a server should reject
+    /// one that does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// assert!(Method::TRACE.is_trace());
+    /// assert!(!Method::OPTIONS.is_trace());
+    /// ```
+    #[inline]
+    pub fn is_trace(&self) -> bool {
+        self.0 == Trace
+    }
+
     /// Return a &str representation of the HTTP method
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -250,7 +452,7 @@ impl Method {
             }
             ExtensionAllocated(ref data) => {
                 unsafe {
-                    str::from_utf8_unchecked(data)
+                    str::from_utf8_unchecked(&data[..])
                 }
             }
         }
@@ -420,3 +622,130 @@ fn test_invalid_method() {
     assert!(Method::from_str("").is_err());
     assert!(Method::from_bytes(b"").is_err());
 }
+
+#[test]
+fn test_expects_request_body() {
+    assert!(!Method::GET.expects_request_body());
+    assert!(!Method::HEAD.expects_request_body());
+    assert!(!Method::DELETE.expects_request_body());
+    assert!(!Method::OPTIONS.expects_request_body());
+    assert!(!Method::TRACE.expects_request_body());
+    assert!(!Method::CONNECT.expects_request_body());
+
+    assert!(Method::POST.expects_request_body());
+    assert!(Method::PUT.expects_request_body());
+    assert!(Method::PATCH.expects_request_body());
+
+    assert!(Method::from_bytes(b"PROPFIND").unwrap().expects_request_body());
+}
+
+#[test]
+fn test_is_options_and_is_trace() {
+    assert!(Method::OPTIONS.is_options());
+    assert!(Method::from_bytes(b"OPTIONS").unwrap().is_options());
+    assert!(!Method::TRACE.is_options());
+    assert!(!Method::from_bytes(b"options").unwrap().is_options());
+
+    assert!(Method::TRACE.is_trace());
+    assert!(Method::from_bytes(b"TRACE").unwrap().is_trace());
+    assert!(!Method::OPTIONS.is_trace());
+    assert!(!Method::from_bytes(b"TRACK").unwrap().is_trace());
+}
+
+#[test]
+fn test_register_semantics() {
+    let method = Method::from_bytes(b"X-REPLAY").unwrap();
+    assert!(!method.is_idempotent());
+
+    Method::register_semantics("X-REPLAY", false, true);
+
+    assert!(!method.is_safe());
+    assert!(method.is_idempotent());
+
+    let long = "X-VERY-LONG-EXTENSION-METHOD";
+    Method::register_semantics(long, true, false);
+
+    let method = Method::from_bytes(long.as_bytes()).unwrap();
+    assert!(method.is_safe());
+    assert!(method.is_idempotent());
+
+    Method::register_semantics("POST", true, true);
+    assert!(!Method::POST.is_safe());
+}
+
+#[test]
+fn test_from_str_standard() {
+    let standard = [
+        Method::OPTIONS,
+        Method::GET,
+        Method::POST,
+        Method::PUT,
+        Method::DELETE,
+        Method::HEAD,
+        Method::TRACE,
+        Method::CONNECT,
+        Method::PATCH,
+    ];
+
+    for method in standard.iter() {
+        assert_eq!(Method::from_str_standard(method.as_str()).as_ref(), Some(method));
+    }
+
+    assert_eq!(Method::from_str_standard("PROPFIND"), None);
+    assert_eq!(Method::from_str_standard("X-VERY-LONG-EXTENSION-METHOD"), None);
+    assert_eq!(Method::from_str_standard("Get"), None);
+    assert_eq!(Method::from_str_standard(""), None);
+
+    // The constants can be matched on.
+    match Method::from_str_standard("DELETE") {
+        Some(Method::DELETE) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_is_safe_and_is_idempotent() {
+    assert!(Method::GET.is_safe());
+    assert!(Method::GET.is_idempotent());
+
+    assert!(!Method::POST.is_safe());
+    assert!(!Method::POST.is_idempotent());
+
+    assert!(!Method::PUT.is_safe());
+    assert!(Method::PUT.is_idempotent());
+
+    assert!(!Method::DELETE.is_safe());
+    assert!(Method::DELETE.is_idempotent());
+
+    assert!(!Method::PATCH.is_safe());
+    assert!(!Method::PATCH.is_idempotent());
+
+    for method in [Method::HEAD, Method::OPTIONS, Method::TRACE].iter() {
+        assert!(method.is_safe(), "{:?}", method);
+        assert!(method.is_idempotent(), "{:?}", method);
+    }
+
+    let custom = Method::from_bytes(b"X-UNREGISTERED").unwrap();
+    assert!(!custom.is_safe());
+    assert!(!custom.is_idempotent());
+}
+
+#[test]
+fn test_from_static() {
+    let propfind = Method::from_static("PROPFIND");
+    assert_eq!(propfind, Method::from_bytes(b"PROPFIND").unwrap());
+    assert_eq!(propfind.as_str(), "PROPFIND");
+
+    let long = "X-VERY-LONG-STATIC-METHOD";
+    let method = Method::from_static(long);
+    assert_eq!(method, Method::from_bytes(long.as_bytes()).unwrap());
+    assert_eq!(method.as_str().as_ptr(), long.as_ptr());
+
+    assert_eq!(Method::from_static("PATCH"), Method::PATCH);
+}
+
+#[test]
+#[should_panic]
+fn test_from_static_invalid() {
+    Method::from_static("BAD METHOD");
+}
@@ -54,10 +54,12 @@
 
 use std::any::Any;
 use std::fmt;
+use std::result;
 
 use {Uri, Error, Result, HttpTryFrom, Extensions};
-use header::{HeaderMap, HeaderName, HeaderValue};
+use header::{self, AsHeaderName, GetAllStr, HeaderMap, HeaderName, HeaderValue, InvalidContentLength};
 use method::Method;
+use uri::{self, Authority};
 use version::Version;
 
 /// Represents an HTTP request.
@@ -178,6 +180,9 @@ pub struct Parts {
     /// The request's extensions
     pub extensions: Extensions,
 
+    /// The request's trailing header fields, if any
+    pub trailers: Option<HeaderMap<HeaderValue>>,
+
     _priv: (),
 }
 
@@ -188,7 +193,7 @@ pub struct Parts {
 #[derive(Debug)]
 pub struct Builder {
     head: Option<Parts>,
-    err: Option<Error>,
+    errors: Vec<Error>,
 }
 
 impl Request<()> {
@@ -565,6 +570,190 @@ impl<T> Request<T> {
         &mut self.head.headers
     }
 
+    /// Returns a reference to the associated trailer field map, if any
+    /// trailers have been attached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request: Request<()> = Request::default();
+    /// assert!(request.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.head.trailers.as_ref()
+    }
+
+    /// Returns a mutable reference to the associated trailer field map.
+    ///
+    /// An empty map is created if no trailers have been attached yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::*;
+    /// let mut request: Request<()> = Request::default();
+    /// request.trailers_mut().insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert_eq!(request.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap<HeaderValue> {
+        self.head.trailers.get_or_insert_with(HeaderMap::new)
+    }
+
+    /// Returns the additional hop-by-hop headers listed by the `Connection`
+    /// header.
+    ///
+    /// Each comma-separated token of the `Connection` header fields is
+    /// parsed as a header name. Invalid tokens, duplicates and the standard
+    /// hop-by-hop headers of `header::HOP_BY_HOP_HEADERS` are skipped. A proxy
+    /// must remove the returned headers, in addition to the standard ones,
+    /// before forwarding the message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::builder()
+    ///     .header("Connection", "keep-alive, X-Custom")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(request.connection_listed_headers(), ["x-custom"]);
+    /// ```
+    pub fn connection_listed_headers(&self) -> Vec<HeaderName> {
+        header::connection_listed_headers(&self.head.headers)
+    }
+
+    /// Returns an iterator over all values of the header `key`, as `&str`s.
+    ///
+    /// Values that are not visible ASCII are silently skipped, as by
+    /// `HeaderMap::get_all_str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::builder()
+    ///     .header("Accept", "text/html")
+    ///     .header("Accept", "application/json")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let accept: Vec<_> = request.header_all_str("accept").collect();
+    /// assert_eq!(accept, ["text/html", "application/json"]);
+    /// ```
+    pub fn header_all_str<'a, K>(&'a self, key: K) -> GetAllStr<'a>
+        where K: AsHeaderName
+    {
+        self.head.headers.get_all_str(key)
+    }
+
+    /// Returns the length of the body, as given by the `Content-Length`
+    /// header.
+    ///
+    /// `Ok(None)` is returned when the header is absent. Repeated values are
+    /// accepted only if they are identical. An error is returned if any value
+    /// is not a non-negative decimal integer or if the values disagree, as
+    /// the message cannot then be framed safely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::builder()
+    ///     .header("Content-Length", "42")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(request.content_length().unwrap(), Some(42));
+    /// ```
+    pub fn content_length(&self) -> result::Result<Option<u64>, InvalidContentLength> {
+        header::content_length(&self.head.headers)
+    }
+
+    /// Returns true if the body uses the chunked transfer coding.
+    ///
+    /// The coding list of the `Transfer-Encoding` header is parsed, and this
+    /// returns true only when its final coding is `chunked`, compared
+    /// case-insensitively. Any other final coding, or an absent header, does
+    /// not delimit the body with chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::builder()
+    ///     .header("Transfer-Encoding", "gzip, chunked")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert!(request.is_chunked());
+    /// ```
+    pub fn is_chunked(&self) -> bool {
+        header::is_chunked(&self.head.headers)
+    }
+
+    /// Returns true if the `Host` header agrees with an absolute-form target.
+    ///
+    /// Per RFC 7230, Section 5.4, when the request target is an absolute URI
+    /// the `Host` header must carry the same authority. The hosts are
+    /// compared case-insensitively, and an omitted port stands for the
+    /// default port of the target's scheme. A `Host` header that is repeated,
+    /// malformed or contains userinfo never matches.
+    ///
+    /// True is returned when there is nothing to check: when the target is
+    /// not absolute-form, or when there is no `Host` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::get("https://example.com:443/")
+    ///     .header("Host", "Example.com")
+    ///     .body(())
+    ///     .unwrap();
+    /// assert!(request.host_matches_target());
+    ///
+    /// let request = Request::get("https://example.com/")
+    ///     .header("Host", "attacker.example")
+    ///     .body(())
+    ///     .unwrap();
+    /// assert!(!request.host_matches_target());
+    /// ```
+    pub fn host_matches_target(&self) -> bool {
+        let uri = &self.head.uri;
+
+        let (scheme, target) = match (uri.scheme_part(), uri.authority_part()) {
+            (Some(scheme), Some(target)) => (scheme, target),
+            _ => return true,
+        };
+
+        let mut values = self.head.headers.get_all(header::HOST).iter();
+
+        let value = match (values.next(), values.next()) {
+            (None, _) => return true,
+            (Some(value), None) => value,
+            (Some(_), Some(_)) => return false,
+        };
+
+        let host: Authority = match value.to_str().ok().and_then(|s| s.parse().ok()) {
+            Some(host) => host,
+            None => return false,
+        };
+
+        if host.userinfo().is_some() {
+            return false;
+        }
+
+        let default_port = scheme.default_port();
+        let port = |authority: &Authority| authority.port_u16().or(default_port);
+
+        host.host().eq_ignore_ascii_case(target.host()) && port(&host) == port(target)
+    }
+
 
     /// Returns a reference to the associated extensions.
     ///
@@ -596,6 +785,27 @@ impl<T> Request<T> {
         &mut self.head.extensions
     }
 
+    /// Inserts an extension, returning the previous extension of the same
+    /// type, if any.
+    ///
+    /// This is a shorthand for `extensions_mut().insert(value)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut request: Request<()> = Request::default();
+    /// assert_eq!(request.replace_extension(1u32), None);
+    /// assert_eq!(request.replace_extension(2u32), Some(1));
+    /// assert_eq!(request.extensions().get::<u32>(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn replace_extension<E>(&mut self, value: E) -> Option<E>
+        where E: Any + Send + Sync + 'static,
+    {
+        self.head.extensions.insert(value)
+    }
+
     /// Returns a reference to the associated HTTP body.
     ///
     /// # Examples
@@ -676,6 +886,28 @@ impl<T> Request<T> {
     {
         Request { body: f(self.body), head: self.head }
     }
+
+    /// Consumes the request returning a new request with the parts mapped by
+    /// the passed in function, and the body unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::get("/old").body("some string").unwrap();
+    /// let rewritten = request.map_parts(|mut parts| {
+    ///     parts.uri = Uri::from_static("/new");
+    ///     parts
+    /// });
+    /// assert_eq!(rewritten.uri(), "/new");
+    /// assert_eq!(rewritten.body(), &"some string");
+    /// ```
+    #[inline]
+    pub fn map_parts<F>(self, f: F) -> Request<T>
+        where F: FnOnce(Parts) -> Parts
+    {
+        Request { head: f(self.head), body: self.body }
+    }
 }
 
 impl<T: Default> Default for Request<T> {
@@ -706,6 +938,7 @@ impl Parts {
             version: Version::default(),
             headers: HeaderMap::default(),
             extensions: Extensions::default(),
+            trailers: None,
             _priv: (),
         }
     }
@@ -718,6 +951,7 @@ impl fmt::Debug for Parts {
             .field("uri", &self.uri)
             .field("version", &self.version)
             .field("headers", &self.headers)
+            .field("trailers", &self.trailers)
             // omits Extensions because not useful
             // omits _priv because not useful
             .finish()
@@ -762,10 +996,10 @@ impl Builder {
     pub fn method<T>(&mut self, method: T) -> &mut Builder
         where Method: HttpTryFrom<T>,
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             match HttpTryFrom::try_from(method) {
                 Ok(s) => head.method = s,
-                Err(e) => self.err = Some(e.into()),
+                Err(e) => self.errors.push(e.into()),
             }
         }
         self
@@ -790,7 +1024,7 @@ impl Builder {
     /// ```
     pub fn method_ref(&self) -> Option<&Method>
     {
-        if self.err.is_some() {
+        if !self.errors.is_empty() {
             return None
         }
         match self.head {
@@ -819,15 +1053,42 @@ impl Builder {
     pub fn uri<T>(&mut self, uri: T) -> &mut Builder
         where Uri: HttpTryFrom<T>,
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             match HttpTryFrom::try_from(uri) {
                 Ok(s) => head.uri = s,
-                Err(e) => self.err = Some(e.into()),
+                Err(e) => self.errors.push(e.into()),
             }
         }
         self
     }
 
+    /// Set the URI for this request from its components.
+    ///
+    /// This is a shorthand for building the `Uri` with `Uri::from_parts` and
+    /// passing it to `uri`. If the parts do not form a valid `Uri`, the
+    /// `InvalidUriParts` error is returned from `Builder::body`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::uri;
+    /// let mut parts = uri::Parts::default();
+    /// parts.scheme = Some("https".parse().unwrap());
+    /// parts.authority = Some("www.rust-lang.org".parse().unwrap());
+    /// parts.path_and_query = Some("/learn".parse().unwrap());
+    ///
+    /// let req = Request::builder()
+    ///     .uri_parts(parts)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(req.uri(), "https://www.rust-lang.org/learn");
+    /// ```
+    pub fn uri_parts(&mut self, parts: uri::Parts) -> &mut Builder {
+        self.uri(parts)
+    }
+
     /// Get the URI for this request
     /// 
     /// By default this is `/`
@@ -843,7 +1104,7 @@ impl Builder {
     /// ```
     pub fn uri_ref(&self) -> Option<&Uri>
     {
-        if self.err.is_some() {
+        if !self.errors.is_empty() {
             return None;
         }
         match self.head
@@ -871,7 +1132,7 @@ impl Builder {
     ///     .unwrap();
     /// ```
     pub fn version(&mut self, version: Version) -> &mut Builder {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             head.version = version;
         }
         self
@@ -899,20 +1160,49 @@ impl Builder {
         where HeaderName: HttpTryFrom<K>,
               HeaderValue: HttpTryFrom<V>
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             match <HeaderName as HttpTryFrom<K>>::try_from(key) {
                 Ok(key) => {
                     match <HeaderValue as HttpTryFrom<V>>::try_from(value) {
                         Ok(value) => { head.headers.append(key, value); }
-                        Err(e) => self.err = Some(e.into()),
+                        Err(e) => self.errors.push(e.into()),
                     }
                 },
-                Err(e) => self.err = Some(e.into()),
+                Err(e) => self.errors.push(e.into()),
             };
         }
         self
     }
 
+    /// Appends a header to this request builder if `cond` is `true`.
+    ///
+    /// When `cond` is `false` the builder is returned unchanged, which keeps
+    /// conditionally configured builders chainable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let req = Request::builder()
+    ///     .header_if(true, "X-Custom-Foo", "bar")
+    ///     .header_if(false, "X-Custom-Bar", "baz")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(req.headers()["X-Custom-Foo"], "bar");
+    /// assert!(!req.headers().contains_key("X-Custom-Bar"));
+    /// ```
+    pub fn header_if<K, V>(&mut self, cond: bool, key: K, value: V) -> &mut Builder
+        where HeaderName: HttpTryFrom<K>,
+              HeaderValue: HttpTryFrom<V>
+    {
+        if cond {
+            self.header(key, value);
+        }
+        self
+    }
+
     /// Get header on this request builder.
     /// when builder has error returns None
     /// 
@@ -930,7 +1220,7 @@ impl Builder {
     /// assert_eq!( headers["X-Custom-Foo"], "bar" );
     /// ```
     pub fn headers_ref(&self) -> Option<&HeaderMap<HeaderValue>> {
-        if self.err.is_some() {
+        if !self.errors.is_empty() {
             return None;
         }
         match self.head
@@ -958,16 +1248,38 @@ impl Builder {
     pub fn extension<T>(&mut self, extension: T) -> &mut Builder
         where T: Any + Send + Sync + 'static,
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             head.extensions.insert(extension);
         }
         self
     }
 
+    /// Returns the errors encountered so far, in order.
+    ///
+    /// The builder does not stop at the first invalid argument: later
+    /// arguments are still validated and, if valid, applied. `body` returns
+    /// all of these errors, combined into one `Error` if there are several.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut req = Request::builder();
+    /// req.header("Bad Name", "a")
+    ///    .header("X-Good", "b")
+    ///    .header("X-Bad-Value", "c\r\n");
+    ///
+    /// assert_eq!(req.errors().len(), 2);
+    /// assert!(req.body(()).is_err());
+    /// ```
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
     fn take_parts(&mut self) -> Result<Parts> {
         let ret = self.head.take().expect("cannot reuse request builder");
-        if let Some(e) = self.err.take() {
-            return Err(e)
+        if !self.errors.is_empty() {
+            return Err(Error::combine(self.errors.drain(..).collect()))
         }
         Ok(ret)
     }
@@ -981,7 +1293,9 @@ impl Builder {
     /// failed to parse or get converted to the internal representation. For
     /// example if an invalid `head` was specified via `header("Foo",
     /// "Bar\r\n")` the error will be returned when this function is called
-    /// rather than when `header` was called.
+    /// rather than when `header` was called. If several arguments were
+    /// invalid, a single `Error` combining all of them is returned, and
+    /// `Error::errors` gives access to each one.
     ///
     /// # Panics
     ///
@@ -1005,12 +1319,7 @@ impl Builder {
     }
 }
 
-fn head<'a>(head: &'a mut Option<Parts>, err: &Option<Error>)
-    -> Option<&'a mut Parts>
-{
-    if err.is_some() {
-        return None
-    }
+fn head<'a>(head: &'a mut Option<Parts>) -> Option<&'a mut Parts> {
     head.as_mut()
 }
 
@@ -1019,7 +1328,7 @@ impl Default for Builder {
     fn default() -> Builder {
         Builder {
             head: Some(Parts::new()),
-            err: None,
+            errors: Vec::new(),
         }
     }
 }
@@ -1037,4 +1346,222 @@ mod tests {
         });
         assert_eq!(mapped_request.body(), &123u32);
     }
+
+    #[test]
+    fn it_can_map_the_parts_keeping_the_body() {
+        #[derive(Debug, PartialEq)]
+        struct Body(Vec<u8>);
+
+        let request = Request::get("http://example.com/old")
+            .body(Body(b"payload".to_vec()))
+            .unwrap();
+
+        let request = request.map_parts(|mut parts| {
+            parts.method = Method::POST;
+            parts.uri = "http://example.com/new".parse().unwrap();
+            parts
+        });
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.uri(), "http://example.com/new");
+        assert_eq!(request.into_body(), Body(b"payload".to_vec()));
+    }
+
+    #[test]
+    fn it_adds_headers_conditionally() {
+        let req = Request::builder()
+            .header_if(true, "x-added", "yes")
+            .header_if(false, "x-skipped", "no")
+            .body(())
+            .unwrap();
+
+        assert_eq!(req.headers().len(), 1);
+        assert_eq!(req.headers()["x-added"], "yes");
+        assert!(req.headers().get("x-skipped").is_none());
+    }
+
+    #[test]
+    fn it_accumulates_builder_errors() {
+        let mut builder = Request::builder();
+        builder
+            .header("Bad Name", "a")
+            .header("x-valid", "b")
+            .header("x-bad-value", "c\r\n");
+
+        assert_eq!(builder.errors().len(), 2);
+
+        {
+            let headers = &builder.head.as_ref().unwrap().headers;
+            assert_eq!(headers.len(), 1);
+            assert_eq!(headers["x-valid"], "b");
+        }
+
+        let err = builder.body(()).unwrap_err();
+        assert!(builder.errors().is_empty());
+
+        let errors = err.errors().expect("several errors are combined");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "invalid HTTP header name");
+        assert_eq!(errors[1].to_string(), "failed to parse header value");
+        assert_eq!(err.to_string(), "invalid HTTP header name; failed to parse header value");
+    }
+
+    #[test]
+    fn it_builds_the_uri_from_parts() {
+        let mut parts = uri::Parts::default();
+        parts.scheme = Some("http".parse().unwrap());
+        parts.authority = Some("example.com:8080".parse().unwrap());
+        parts.path_and_query = Some("/a?b=c".parse().unwrap());
+
+        let req = Request::builder()
+            .method("GET")
+            .uri_parts(parts)
+            .body(())
+            .unwrap();
+
+        assert_eq!(req.uri(), "http://example.com:8080/a?b=c");
+        assert_eq!(req.uri().port_part().unwrap().as_u16(), 8080);
+
+        // A scheme without an authority is not a valid `Uri`.
+        let mut parts = uri::Parts::default();
+        parts.scheme = Some("http".parse().unwrap());
+        parts.path_and_query = Some("/".parse().unwrap());
+
+        let mut builder = Request::builder();
+        builder.uri_parts(parts);
+
+        assert_eq!(builder.errors().len(), 1);
+        assert!(builder.body(()).is_err());
+    }
+
+    #[test]
+    fn it_replaces_an_extension() {
+        #[derive(Debug, PartialEq)]
+        struct Token(&'static str);
+
+        let mut request = Request::new(());
+        assert_eq!(request.extensions_mut().insert(Token("a")), None);
+
+        assert_eq!(request.replace_extension(Token("b")), Some(Token("a")));
+        assert_eq!(request.extensions().get::<Token>(), Some(&Token("b")));
+
+        assert_eq!(request.extensions_mut().insert(Token("c")), Some(Token("b")));
+    }
+
+    #[test]
+    fn it_carries_trailers_through_parts() {
+        let mut request = Request::new(());
+        assert!(request.trailers().is_none());
+
+        request.trailers_mut().insert("x-checksum", "abc".parse().unwrap());
+        request.trailers_mut().append("x-checksum", "def".parse().unwrap());
+
+        let (parts, body) = request.into_parts();
+        assert_eq!(parts.trailers.as_ref().unwrap().len(), 2);
+
+        let request = Request::from_parts(parts, body);
+        let trailers = request.trailers().unwrap();
+        let values: Vec<_> = trailers.get_all("x-checksum").iter().collect();
+        assert_eq!(values, ["abc", "def"]);
+        assert!(request.headers().is_empty());
+    }
+
+    #[test]
+    fn it_lists_connection_headers() {
+        let request = Request::builder()
+            .header("Connection", "keep-alive, X-Custom")
+            .header("Connection", " close ,, x-custom,Bad Token, upgrade")
+            .body(())
+            .unwrap();
+
+        let names = request.connection_listed_headers();
+        assert_eq!(names, ["x-custom", "close"]);
+
+        let request = Request::new(());
+        assert!(request.connection_listed_headers().is_empty());
+    }
+
+    #[test]
+    fn it_reads_the_content_length() {
+        fn content_length(values: &[&str]) -> result::Result<Option<u64>, InvalidContentLength> {
+            let mut builder = Request::builder();
+            for value in values {
+                builder.header("Content-Length", *value);
+            }
+            builder.body(()).unwrap().content_length()
+        }
+
+        assert_eq!(content_length(&[]).unwrap(), None);
+        assert_eq!(content_length(&["1234"]).unwrap(), Some(1234));
+        assert_eq!(content_length(&["10", "10"]).unwrap(), Some(10));
+        assert!(content_length(&["10", "11"]).is_err());
+        assert!(content_length(&["ten"]).is_err());
+        assert!(content_length(&["-10"]).is_err());
+    }
+
+    #[test]
+    fn it_detects_chunked_transfer_coding() {
+        fn is_chunked(value: Option<&str>) -> bool {
+            let mut builder = Request::builder();
+            if let Some(value) = value {
+                builder.header("Transfer-Encoding", value);
+            }
+            builder.body(()).unwrap().is_chunked()
+        }
+
+        assert!(is_chunked(Some("chunked")));
+        assert!(is_chunked(Some("gzip, chunked")));
+        assert!(!is_chunked(Some("chunked, gzip")));
+        assert!(!is_chunked(None));
+    }
+
+    #[test]
+    fn it_reads_all_string_values_of_a_header() {
+        let mut request = Request::new(());
+        {
+            let headers = request.headers_mut();
+            headers.append("accept", HeaderValue::from_static("text/html"));
+            headers.append("accept", HeaderValue::from_bytes(b"text/\xfa").unwrap());
+            headers.append("accept", HeaderValue::from_static("application/json"));
+        }
+
+        let accept: Vec<_> = request.header_all_str("Accept").collect();
+        assert_eq!(accept, ["text/html", "application/json"]);
+
+        assert_eq!(request.header_all_str("content-type").count(), 0);
+    }
+
+    #[test]
+    fn it_checks_host_against_the_target() {
+        fn matches(uri: &str, hosts: &[&str]) -> bool {
+            let mut builder = Request::builder();
+            builder.uri(uri);
+            for host in hosts {
+                builder.header("Host", *host);
+            }
+            builder.body(()).unwrap().host_matches_target()
+        }
+
+        // Matching
+        assert!(matches("http://example.com/a", &["example.com"]));
+        assert!(matches("http://example.com/a", &["EXAMPLE.COM"]));
+        assert!(matches("http://example.com:80/a", &["example.com"]));
+        assert!(matches("https://example.com/a", &["example.com:443"]));
+        assert!(matches("http://user@example.com:8080/", &["example.com:8080"]));
+        assert!(matches("http://[::1]:3000/", &["[::1]:3000"]));
+
+        // Mismatching
+        assert!(!matches("http://example.com/a", &["other.com"]));
+        assert!(!matches("http://example.com/a", &["example.com:8080"]));
+        assert!(!matches("https://example.com/a", &["example.com:80"]));
+        assert!(!matches("http://example.com/a", &["example.com", "example.com"]));
+        assert!(!matches("http://example.com/a", &["user@example.com"]));
+        assert!(!matches("http://example.com/a", &[""]));
+
+        // Nothing to check
+        assert!(matches("/a", &["example.com"]));
+        assert!(matches("*", &["example.com"]));
+        assert!(matches("example.com:443", &["other.com"]));
+        assert!(matches("http://example.com/a", &[]));
+    }
 }
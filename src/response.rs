@@ -63,9 +63,10 @@
 
 use std::any::Any;
 use std::fmt;
+use std::result;
 
 use {Error, Result, HttpTryFrom, Extensions};
-use header::{HeaderMap, HeaderName, HeaderValue};
+use header::{self, AsHeaderName, GetAllStr, HeaderMap, HeaderName, HeaderValue, InvalidContentLength};
 use status::StatusCode;
 use version::Version;
 
@@ -197,6 +198,12 @@ pub struct Parts {
     /// The response's extensions
     pub extensions: Extensions,
 
+    /// The response's trailing header fields, if any
+    pub trailers: Option<HeaderMap<HeaderValue>>,
+
+    /// The response's custom reason phrase, if any
+    pub reason: Option<HeaderValue>,
+
     _priv: (),
 }
 
@@ -207,7 +214,7 @@ pub struct Parts {
 #[derive(Debug)]
 pub struct Builder {
     head: Option<Parts>,
-    err: Option<Error>,
+    errors: Vec<Error>,
 }
 
 impl Response<()> {
@@ -307,6 +314,34 @@ impl<T> Response<T> {
         &mut self.head.status
     }
 
+    /// Returns the reason phrase of this response.
+    ///
+    /// This is the custom reason phrase set with `Builder::reason`, if any,
+    /// and otherwise the canonical reason of the status code. `None` is
+    /// returned if there is neither, or if the custom reason phrase is not
+    /// visible ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response: Response<()> = Response::default();
+    /// assert_eq!(response.reason(), Some("OK"));
+    ///
+    /// let response = Response::builder()
+    ///     .status(418)
+    ///     .reason("Teapot Brewing")
+    ///     .body(())
+    ///     .unwrap();
+    /// assert_eq!(response.reason(), Some("Teapot Brewing"));
+    /// ```
+    pub fn reason(&self) -> Option<&str> {
+        match self.head.reason {
+            Some(ref reason) => reason.to_str().ok(),
+            None => self.head.status.canonical_reason(),
+        }
+    }
+
     /// Returns a reference to the associated version.
     ///
     /// # Examples
@@ -366,6 +401,132 @@ impl<T> Response<T> {
         &mut self.head.headers
     }
 
+    /// Returns a reference to the associated trailer field map, if any
+    /// trailers have been attached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response: Response<()> = Response::default();
+    /// assert!(response.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.head.trailers.as_ref()
+    }
+
+    /// Returns a mutable reference to the associated trailer field map.
+    ///
+    /// An empty map is created if no trailers have been attached yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::*;
+    /// let mut response: Response<()> = Response::default();
+    /// response.trailers_mut().insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert_eq!(response.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap<HeaderValue> {
+        self.head.trailers.get_or_insert_with(HeaderMap::new)
+    }
+
+    /// Returns the additional hop-by-hop headers listed by the `Connection`
+    /// header.
+    ///
+    /// Each comma-separated token of the `Connection` header fields is
+    /// parsed as a header name. Invalid tokens, duplicates and the standard
+    /// hop-by-hop headers of `header::HOP_BY_HOP_HEADERS` are skipped. A proxy
+    /// must remove the returned headers, in addition to the standard ones,
+    /// before forwarding the message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .header("Connection", "keep-alive, X-Custom")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.connection_listed_headers(), ["x-custom"]);
+    /// ```
+    pub fn connection_listed_headers(&self) -> Vec<HeaderName> {
+        header::connection_listed_headers(&self.head.headers)
+    }
+
+    /// Returns an iterator over all values of the header `key`, as `&str`s.
+    ///
+    /// Values that are not visible ASCII are silently skipped, as by
+    /// `HeaderMap::get_all_str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .header("Accept", "text/html")
+    ///     .header("Accept", "application/json")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let accept: Vec<_> = response.header_all_str("accept").collect();
+    /// assert_eq!(accept, ["text/html", "application/json"]);
+    /// ```
+    pub fn header_all_str<'a, K>(&'a self, key: K) -> GetAllStr<'a>
+        where K: AsHeaderName
+    {
+        self.head.headers.get_all_str(key)
+    }
+
+    /// Returns the length of the body, as given by the `Content-Length`
+    /// header.
+    ///
+    /// `Ok(None)` is returned when the header is absent. Repeated values are
+    /// accepted only if they are identical. An error is returned if any value
+    /// is not a non-negative decimal integer or if the values disagree, as
+    /// the message cannot then be framed safely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .header("Content-Length", "42")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.content_length().unwrap(), Some(42));
+    /// ```
+    pub fn content_length(&self) -> result::Result<Option<u64>, InvalidContentLength> {
+        header::content_length(&self.head.headers)
+    }
+
+    /// Returns true if the body uses the chunked transfer coding.
+    ///
+    /// The coding list of the `Transfer-Encoding` header is parsed, and this
+    /// returns true only when its final coding is `chunked`, compared
+    /// case-insensitively. Any other final coding, or an absent header, does
+    /// not delimit the body with chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .header("Transfer-Encoding", "gzip, chunked")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert!(response.is_chunked());
+    /// ```
+    pub fn is_chunked(&self) -> bool {
+        header::is_chunked(&self.head.headers)
+    }
+
     /// Returns a reference to the associated extensions.
     ///
     /// # Examples
@@ -396,6 +557,27 @@ impl<T> Response<T> {
         &mut self.head.extensions
     }
 
+    /// Inserts an extension, returning the previous extension of the same
+    /// type, if any.
+    ///
+    /// This is a shorthand for `extensions_mut().insert(value)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut response: Response<()> = Response::default();
+    /// assert_eq!(response.replace_extension(1u32), None);
+    /// assert_eq!(response.replace_extension(2u32), Some(1));
+    /// assert_eq!(response.extensions().get::<u32>(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn replace_extension<E>(&mut self, value: E) -> Option<E>
+        where E: Any + Send + Sync + 'static,
+    {
+        self.head.extensions.insert(value)
+    }
+
     /// Returns a reference to the associated HTTP body.
     ///
     /// # Examples
@@ -475,6 +657,28 @@ impl<T> Response<T> {
     {
         Response { body: f(self.body), head: self.head }
     }
+
+    /// Consumes the response returning a new response with the parts mapped
+    /// by the passed in function, and the body unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder().body("some string").unwrap();
+    /// let rewritten = response.map_parts(|mut parts| {
+    ///     parts.status = StatusCode::ACCEPTED;
+    ///     parts
+    /// });
+    /// assert_eq!(rewritten.status(), StatusCode::ACCEPTED);
+    /// assert_eq!(rewritten.body(), &"some string");
+    /// ```
+    #[inline]
+    pub fn map_parts<F>(self, f: F) -> Response<T>
+        where F: FnOnce(Parts) -> Parts
+    {
+        Response { head: f(self.head), body: self.body }
+    }
 }
 
 impl<T: Default> Default for Response<T> {
@@ -504,6 +708,8 @@ impl Parts {
             version: Version::default(),
             headers: HeaderMap::default(),
             extensions: Extensions::default(),
+            trailers: None,
+            reason: None,
             _priv: (),
         }
     }
@@ -515,6 +721,8 @@ impl fmt::Debug for Parts {
             .field("status", &self.status)
             .field("version", &self.version)
             .field("headers", &self.headers)
+            .field("trailers", &self.trailers)
+            .field("reason", &self.reason)
             // omits Extensions because not useful
             // omits _priv because not useful
             .finish()
@@ -560,10 +768,10 @@ impl Builder {
     pub fn status<T>(&mut self, status: T) -> &mut Builder
         where StatusCode: HttpTryFrom<T>,
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             match HttpTryFrom::try_from(status) {
                 Ok(s) => head.status = s,
-                Err(e) => self.err = Some(e.into()),
+                Err(e) => self.errors.push(e.into()),
             }
         }
         self
@@ -587,7 +795,7 @@ impl Builder {
     ///     .unwrap();
     /// ```
     pub fn version(&mut self, version: Version) -> &mut Builder {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             head.version = version;
         }
         self
@@ -616,20 +824,49 @@ impl Builder {
         where HeaderName: HttpTryFrom<K>,
               HeaderValue: HttpTryFrom<V>
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             match <HeaderName as HttpTryFrom<K>>::try_from(key) {
                 Ok(key) => {
                     match <HeaderValue as HttpTryFrom<V>>::try_from(value) {
                         Ok(value) => { head.headers.append(key, value); }
-                        Err(e) => self.err = Some(e.into()),
+                        Err(e) => self.errors.push(e.into()),
                     }
                 },
-                Err(e) => self.err = Some(e.into()),
+                Err(e) => self.errors.push(e.into()),
             };
         }
         self
     }
 
+    /// Appends a header to this response builder if `cond` is `true`.
+    ///
+    /// When `cond` is `false` the builder is returned unchanged, which keeps
+    /// conditionally configured builders chainable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .header_if(true, "X-Custom-Foo", "bar")
+    ///     .header_if(false, "X-Custom-Bar", "baz")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()["X-Custom-Foo"], "bar");
+    /// assert!(!response.headers().contains_key("X-Custom-Bar"));
+    /// ```
+    pub fn header_if<K, V>(&mut self, cond: bool, key: K, value: V) -> &mut Builder
+        where HeaderName: HttpTryFrom<K>,
+              HeaderValue: HttpTryFrom<V>
+    {
+        if cond {
+            self.header(key, value);
+        }
+        self
+    }
+
     /// Adds an extension to this builder
     ///
     /// # Examples
@@ -648,16 +885,68 @@ impl Builder {
     pub fn extension<T>(&mut self, extension: T) -> &mut Builder
         where T: Any + Send + Sync + 'static,
     {
-        if let Some(head) = head(&mut self.head, &self.err) {
+        if let Some(head) = head(&mut self.head) {
             head.extensions.insert(extension);
         }
         self
     }
 
+    /// Set a custom reason phrase for this response.
+    ///
+    /// The reason phrase is only sent on the HTTP/1 status line. It has the
+    /// same syntax as a header value, so a phrase containing `\r` or `\n` is
+    /// rejected. By default, the canonical reason of the status is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .reason("Everything Is Fine")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.reason(), Some("Everything Is Fine"));
+    /// ```
+    pub fn reason<T>(&mut self, reason: T) -> &mut Builder
+        where HeaderValue: HttpTryFrom<T>,
+    {
+        if let Some(head) = head(&mut self.head) {
+            match <HeaderValue as HttpTryFrom<T>>::try_from(reason) {
+                Ok(reason) => head.reason = Some(reason),
+                Err(e) => self.errors.push(e.into()),
+            }
+        }
+        self
+    }
+
+    /// Returns the errors encountered so far, in order.
+    ///
+    /// The builder does not stop at the first invalid argument: later
+    /// arguments are still validated and, if valid, applied. `body` returns
+    /// all of these errors, combined into one `Error` if there are several.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut res = Response::builder();
+    /// res.header("Bad Name", "a")
+    ///    .header("X-Good", "b")
+    ///    .header("X-Bad-Value", "c\r\n");
+    ///
+    /// assert_eq!(res.errors().len(), 2);
+    /// assert!(res.body(()).is_err());
+    /// ```
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
     fn take_parts(&mut self) -> Result<Parts> {
         let ret = self.head.take().expect("cannot reuse response builder");
-        if let Some(e) = self.err.take() {
-            return Err(e)
+        if !self.errors.is_empty() {
+            return Err(Error::combine(self.errors.drain(..).collect()))
         }
         Ok(ret)
     }
@@ -671,7 +960,9 @@ impl Builder {
     /// failed to parse or get converted to the internal representation. For
     /// example if an invalid `head` was specified via `header("Foo",
     /// "Bar\r\n")` the error will be returned when this function is called
-    /// rather than when `header` was called.
+    /// rather than when `header` was called. If several arguments were
+    /// invalid, a single `Error` combining all of them is returned, and
+    /// `Error::errors` gives access to each one.
     ///
     /// # Panics
     ///
@@ -695,12 +986,7 @@ impl Builder {
     }
 }
 
-fn head<'a>(head: &'a mut Option<Parts>, err: &Option<Error>)
-    -> Option<&'a mut Parts>
-{
-    if err.is_some() {
-        return None
-    }
+fn head<'a>(head: &'a mut Option<Parts>) -> Option<&'a mut Parts> {
     head.as_mut()
 }
 
@@ -709,7 +995,7 @@ impl Default for Builder {
     fn default() -> Builder {
         Builder {
             head: Some(Parts::new()),
-            err: None,
+            errors: Vec::new(),
         }
     }
 }
@@ -727,4 +1013,187 @@ mod tests {
         });
         assert_eq!(mapped_response.body(), &123u32);
     }
+
+    #[test]
+    fn it_can_map_the_parts_keeping_the_body() {
+        #[derive(Debug, PartialEq)]
+        struct Body(Vec<u8>);
+
+        let response = Response::builder()
+            .status(200)
+            .body(Body(b"payload".to_vec()))
+            .unwrap();
+
+        let response = response.map_parts(|mut parts| {
+            parts.status = StatusCode::NOT_FOUND;
+            parts.headers.insert("x-rewritten", "1".parse().unwrap());
+            parts
+        });
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()["x-rewritten"], "1");
+        assert_eq!(response.into_body(), Body(b"payload".to_vec()));
+    }
+
+    #[test]
+    fn it_adds_headers_conditionally() {
+        let response = Response::builder()
+            .header_if(true, "x-added", "yes")
+            .header_if(false, "x-skipped", "no")
+            .body(())
+            .unwrap();
+
+        assert_eq!(response.headers().len(), 1);
+        assert_eq!(response.headers()["x-added"], "yes");
+        assert!(response.headers().get("x-skipped").is_none());
+    }
+
+    #[test]
+    fn it_accumulates_builder_errors() {
+        let mut builder = Response::builder();
+        builder
+            .header("Bad Name", "a")
+            .header("x-valid", "b")
+            .header("x-bad-value", "c\r\n");
+
+        assert_eq!(builder.errors().len(), 2);
+
+        {
+            let headers = &builder.head.as_ref().unwrap().headers;
+            assert_eq!(headers.len(), 1);
+            assert_eq!(headers["x-valid"], "b");
+        }
+
+        let err = builder.body(()).unwrap_err();
+        assert!(builder.errors().is_empty());
+
+        let errors = err.errors().expect("several errors are combined");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "invalid HTTP header name");
+        assert_eq!(errors[1].to_string(), "failed to parse header value");
+        assert_eq!(err.to_string(), "invalid HTTP header name; failed to parse header value");
+    }
+
+    #[test]
+    fn it_replaces_an_extension() {
+        #[derive(Debug, PartialEq)]
+        struct Token(&'static str);
+
+        let mut response = Response::new(());
+        assert_eq!(response.extensions_mut().insert(Token("a")), None);
+
+        assert_eq!(response.replace_extension(Token("b")), Some(Token("a")));
+        assert_eq!(response.extensions().get::<Token>(), Some(&Token("b")));
+
+        assert_eq!(response.extensions_mut().insert(Token("c")), Some(Token("b")));
+    }
+
+    #[test]
+    fn it_carries_trailers_through_parts() {
+        let mut response = Response::new(());
+        assert!(response.trailers().is_none());
+
+        response.trailers_mut().insert("x-checksum", "abc".parse().unwrap());
+        response.trailers_mut().append("x-checksum", "def".parse().unwrap());
+
+        let (parts, body) = response.into_parts();
+        assert_eq!(parts.trailers.as_ref().unwrap().len(), 2);
+
+        let response = Response::from_parts(parts, body);
+        let trailers = response.trailers().unwrap();
+        let values: Vec<_> = trailers.get_all("x-checksum").iter().collect();
+        assert_eq!(values, ["abc", "def"]);
+        assert!(response.headers().is_empty());
+    }
+
+    #[test]
+    fn it_sets_a_custom_reason_phrase() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .reason("Teapot Brewing")
+            .body(())
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.reason(), Some("Teapot Brewing"));
+
+        let (parts, _) = response.into_parts();
+        assert_eq!(parts.reason.unwrap(), "Teapot Brewing");
+
+        let response = Response::builder().status(404).body(()).unwrap();
+        assert_eq!(response.reason(), Some("Not Found"));
+
+        let response = Response::builder().status(599).body(()).unwrap();
+        assert_eq!(response.reason(), None);
+
+        let result = Response::builder()
+            .reason("Teapot\rBrewing")
+            .body(());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_lists_connection_headers() {
+        let response = Response::builder()
+            .header("Connection", "keep-alive, X-Custom")
+            .header("Connection", " close ,, x-custom,Bad Token, upgrade")
+            .body(())
+            .unwrap();
+
+        let names = response.connection_listed_headers();
+        assert_eq!(names, ["x-custom", "close"]);
+
+        let response = Response::new(());
+        assert!(response.connection_listed_headers().is_empty());
+    }
+
+    #[test]
+    fn it_reads_the_content_length() {
+        fn content_length(values: &[&str]) -> result::Result<Option<u64>, InvalidContentLength> {
+            let mut builder = Response::builder();
+            for value in values {
+                builder.header("Content-Length", *value);
+            }
+            builder.body(()).unwrap().content_length()
+        }
+
+        assert_eq!(content_length(&[]).unwrap(), None);
+        assert_eq!(content_length(&["1234"]).unwrap(), Some(1234));
+        assert_eq!(content_length(&["10", "10"]).unwrap(), Some(10));
+        assert!(content_length(&["10", "11"]).is_err());
+        assert!(content_length(&["ten"]).is_err());
+        assert!(content_length(&["-10"]).is_err());
+    }
+
+    #[test]
+    fn it_detects_chunked_transfer_coding() {
+        fn is_chunked(value: Option<&str>) -> bool {
+            let mut builder = Response::builder();
+            if let Some(value) = value {
+                builder.header("Transfer-Encoding", value);
+            }
+            builder.body(()).unwrap().is_chunked()
+        }
+
+        assert!(is_chunked(Some("chunked")));
+        assert!(is_chunked(Some("gzip, chunked")));
+        assert!(!is_chunked(Some("chunked, gzip")));
+        assert!(!is_chunked(None));
+    }
+
+    #[test]
+    fn it_reads_all_string_values_of_a_header() {
+        let mut response = Response::new(());
+        {
+            let headers = response.headers_mut();
+            headers.append("accept", HeaderValue::from_static("text/html"));
+            headers.append("accept", HeaderValue::from_bytes(b"text/\xfa").unwrap());
+            headers.append("accept", HeaderValue::from_static("application/json"));
+        }
+
+        let accept: Vec<_> = response.header_all_str("Accept").collect();
+        assert_eq!(accept, ["text/html", "application/json"]);
+
+        assert_eq!(response.header_all_str("content-type").count(), 0);
+    }
 }
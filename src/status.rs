@@ -51,6 +51,29 @@ pub struct InvalidStatusCode {
     _priv: (),
 }
 
+/// The class of a `StatusCode`, given by its first digit.
+///
+/// Returned by `StatusCode::class`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusClass {
+    /// 1xx: the request was received, continuing process.
+    Informational,
+    /// 2xx: the request was successfully received, understood, and accepted.
+    Success,
+    /// 3xx: further action needs to be taken to complete the request.
+    Redirection,
+    /// 4xx: the request contains bad syntax or cannot be fulfilled.
+    ClientError,
+    /// 5xx: the server failed to fulfill an apparently valid request.
+    ServerError,
+}
+
+/// Displays a `StatusCode` along with its canonical reason phrase.
+///
+/// Returned by `StatusCode::display_with_reason`.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWithReason(StatusCode);
+
 impl StatusCode {
     /// Converts a u16 to a status code.
     ///
@@ -153,6 +176,40 @@ impl StatusCode {
     }
 
 
+    /// Returns the class of the status code, its first digit, between 1 and 5.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert_eq!(StatusCode::NOT_FOUND.class_digit(), 4);
+    /// ```
+    #[inline]
+    pub fn class_digit(&self) -> u8 {
+        (self.0 / 100) as u8
+    }
+
+    /// Returns the class of this status code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// # use http::status::StatusClass;
+    /// assert_eq!(StatusCode::OK.class(), StatusClass::Success);
+    /// assert_eq!(StatusCode::NOT_FOUND.class(), StatusClass::ClientError);
+    /// ```
+    pub fn class(&self) -> StatusClass {
+        match self.class_digit() {
+            1 => StatusClass::Informational,
+            2 => StatusClass::Success,
+            3 => StatusClass::Redirection,
+            4 => StatusClass::ClientError,
+            5 => StatusClass::ServerError,
+            _ => unreachable!("status code out of range: {}", self.0),
+        }
+    }
+
     /// Check if status is within 100-199.
     #[inline]
     pub fn is_informational(&self) -> bool {
@@ -182,6 +239,70 @@ impl StatusCode {
     pub fn is_server_error(&self) -> bool {
         600 > self.0 && self.0 >= 500
     }
+
+    /// Check if status is within 400-599, i.e. either a client or a server
+    /// error.
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.is_client_error() || self.is_server_error()
+    }
+
+    /// Converts the status into a `Result`, returning `Err(self)` if it is a
+    /// client or server error and `Ok(self)` otherwise.
+    ///
+    /// This allows short-circuiting on error statuses with `?`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert_eq!(StatusCode::OK.err(), Ok(StatusCode::OK));
+    /// assert_eq!(StatusCode::NOT_FOUND.err(), Err(StatusCode::NOT_FOUND));
+    /// ```
+    #[inline]
+    pub fn err(self) -> Result<StatusCode, StatusCode> {
+        if self.is_error() {
+            Err(self)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Check if a response with this status must not include a message body.
+    ///
+    /// Per RFC 7230, Section 3.3.3, this is the case for all 1xx
+    /// (Informational), 204 (No Content) and 304 (Not Modified) responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert!(StatusCode::NO_CONTENT.must_have_empty_body());
+    /// assert!(!StatusCode::OK.must_have_empty_body());
+    /// ```
+    #[inline]
+    pub fn must_have_empty_body(&self) -> bool {
+        self.is_informational() || self.0 == 204 || self.0 == 304
+    }
+
+    /// Returns a value that displays the status code followed by its
+    /// canonical reason phrase, as in `404 Not Found`.
+    ///
+    /// Unlike the `Display` implementation of `StatusCode`, the reason is
+    /// omitted entirely when the status code has no canonical reason.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert_eq!(StatusCode::NOT_FOUND.display_with_reason().to_string(), "404 Not Found");
+    ///
+    /// let status = StatusCode::from_u16(599).unwrap();
+    /// assert_eq!(status.display_with_reason().to_string(), "599");
+    /// ```
+    pub fn display_with_reason(&self) -> DisplayWithReason {
+        DisplayWithReason(*self)
+    }
 }
 
 impl fmt::Debug for StatusCode {
@@ -205,6 +326,15 @@ impl fmt::Display for StatusCode {
     }
 }
 
+impl fmt::Display for DisplayWithReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.canonical_reason() {
+            Some(reason) => write!(f, "{} {}", self.0.as_u16(), reason),
+            None => write!(f, "{}", self.0.as_u16()),
+        }
+    }
+}
+
 impl Default for StatusCode {
     #[inline]
     fn default() -> StatusCode {
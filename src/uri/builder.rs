@@ -95,6 +95,33 @@ impl Builder {
         })
     }
 
+    /// Remove the query component from the `PathAndQuery` configured so far.
+    ///
+    /// The path is left untouched. Since `Uri` does not retain fragments,
+    /// there is no fragment to clear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let uri = uri::Builder::new()
+    ///     .path_and_query("/hello?foo=bar")
+    ///     .clear_query()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(uri, "/hello");
+    /// ```
+    pub fn clear_query(&mut self) -> &mut Self {
+        self.map(|parts| {
+            if let Some(ref mut path_and_query) = parts.path_and_query {
+                *path_and_query = path_and_query.without_query();
+            }
+            Ok(())
+        })
+    }
+
     /// Consumes this builder, and tries to construct a valid `Uri` from
     /// the configured pieces.
     ///
@@ -108,7 +135,9 @@ impl Builder {
     ///
     /// Additionally, the various forms of URI require certain combinations of
     /// parts to be set to be valid. If the parts don't fit into any of the
-    /// valid forms of URI, a new error is returned.
+    /// valid forms of URI, a new error is returned. In particular, a `scheme`
+    /// without an `authority` is always rejected, since `Uri` cannot
+    /// represent authority-less absolute URIs such as `mailto:` ones.
     ///
     /// # Examples
     ///
@@ -118,6 +147,12 @@ impl Builder {
     /// let uri = Uri::builder()
     ///     .build()
     ///     .unwrap();
+    ///
+    /// let err = Uri::builder()
+    ///     .scheme("https")
+    ///     .path_and_query("/")
+    ///     .build();
+    /// assert!(err.is_err());
     /// ```
     pub fn build(&mut self) -> Result<Uri> {
         self
@@ -24,14 +24,17 @@
 
 use HttpTryFrom;
 use byte_str::ByteStr;
+use header::HeaderValue;
 
 use bytes::Bytes;
 
 use std::{fmt, u8, u16};
+use std::borrow::Cow;
 // Deprecated in 1.26, needed until our minimum version is >=1.23.
 #[allow(unused, deprecated)]
 use std::ascii::AsciiExt;
 use std::hash::{Hash, Hasher};
+use std::net::Ipv4Addr;
 use std::str::{self, FromStr};
 use std::error::Error;
 use std::convert::TryFrom;
@@ -41,13 +44,16 @@ use self::scheme::Scheme2;
 pub use self::authority::Authority;
 pub use self::builder::Builder;
 pub use self::path::PathAndQuery;
+pub use self::percent::{encode_path_segment, encode_query};
 pub use self::scheme::Scheme;
 pub use self::port::Port;
 
 mod authority;
 mod builder;
 mod path;
+mod percent;
 mod port;
+mod resolve;
 mod scheme;
 #[cfg(test)]
 mod tests;
@@ -121,6 +127,22 @@ pub struct Parts {
     _priv: (),
 }
 
+/// An iterator over the percent-decoded segments of a URI's path.
+///
+/// This struct is created by the `path_segments` method on `Uri`.
+#[derive(Debug)]
+pub struct PathSegments<'a> {
+    segments: str::Split<'a, char>,
+}
+
+/// An iterator over the percent-decoded `key=value` pairs of a URI's query.
+///
+/// This struct is created by the `query_pairs` method on `Uri`.
+#[derive(Debug)]
+pub struct QueryPairs<'a> {
+    pairs: str::Split<'a, char>,
+}
+
 /// An error resulting from a failed attempt to construct a URI.
 #[derive(Debug)]
 pub struct InvalidUri(ErrorKind);
@@ -146,6 +168,9 @@ enum ErrorKind {
     TooLong,
     Empty,
     SchemeTooLong,
+    HostTooLong,
+    LabelTooLong,
+    PortOutOfRange,
 }
 
 // u16::MAX is reserved for None
@@ -204,19 +229,36 @@ impl Uri {
     }
 
     /// Attempt to convert a `Uri` from `Parts`
+    ///
+    /// The path must be one that can be displayed and parsed back into an
+    /// equal `Uri`: an absolute URI's path must begin with `/`, and a path
+    /// on its own must either be `*` or begin with a single `/`.
     pub fn from_parts(src: Parts) -> Result<Uri, InvalidUriParts> {
         if src.scheme.is_some() {
             if src.authority.is_none() {
                 return Err(ErrorKind::AuthorityMissing.into());
             }
 
-            if src.path_and_query.is_none() {
-                return Err(ErrorKind::PathAndQueryMissing.into());
+            match src.path_and_query {
+                Some(ref path_and_query) => {
+                    if !path_and_query.path().starts_with('/') {
+                        return Err(ErrorKind::InvalidFormat.into());
+                    }
+                }
+                None => return Err(ErrorKind::PathAndQueryMissing.into()),
             }
         } else {
             if src.authority.is_some() && src.path_and_query.is_some() {
                 return Err(ErrorKind::SchemeMissing.into());
             }
+
+            if let Some(ref path_and_query) = src.path_and_query {
+                let path = path_and_query.path();
+
+                if path.starts_with("//") || !(path.starts_with('/') || path == "*") {
+                    return Err(ErrorKind::InvalidFormat.into());
+                }
+            }
         }
 
         let scheme = match src.scheme {
@@ -292,6 +334,83 @@ impl Uri {
         }
     }
 
+    /// Parse `input` as a URI reference and resolve it against `base`.
+    ///
+    /// Relative references, such as `../x` or `?query`, are resolved using
+    /// the algorithm described in RFC 3986, Section 5.2. If `input` is an
+    /// absolute URI, `base` is ignored apart from dot-segment removal being
+    /// applied to the path of `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let base: Uri = "http://example.com/a/b/c".parse().unwrap();
+    ///
+    /// let uri = Uri::parse_with_base(&base, "../x").unwrap();
+    /// assert_eq!(uri, "http://example.com/a/x");
+    ///
+    /// let uri = Uri::parse_with_base(&base, "https://rust-lang.org/").unwrap();
+    /// assert_eq!(uri, "https://rust-lang.org/");
+    /// ```
+    pub fn parse_with_base(base: &Uri, input: &str) -> Result<Uri, InvalidUri> {
+        resolve::resolve_str(base, input)
+    }
+
+    /// Resolve `reference` against this `Uri`, as described by RFC 3986,
+    /// Section 5.2.
+    ///
+    /// This is how a `Location` header or a link is turned into the URI to
+    /// follow. A scheme-relative reference such as `//host/path` takes the
+    /// scheme of this `Uri`, an absolute-path reference takes its scheme and
+    /// authority, and a reference with only a query also keeps its path. Dot
+    /// segments are removed from the resulting path. An absolute `reference`
+    /// is returned unchanged.
+    ///
+    /// References with a relative path, such as `../x`, cannot be parsed as
+    /// a `Uri`; use `parse_with_base` to resolve those. A `Uri` with only an
+    /// authority, such as `example.com`, is treated as `//example.com`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let base: Uri = "http://example.com/a/b?q".parse().unwrap();
+    ///
+    /// let location: Uri = "/c/./d".parse().unwrap();
+    /// assert_eq!(base.resolve(&location).unwrap(), "http://example.com/c/d");
+    ///
+    /// let location: Uri = "//other.com/x".parse().unwrap();
+    /// assert_eq!(base.resolve(&location).unwrap(), "http://other.com/x");
+    ///
+    /// let location: Uri = "https://rust-lang.org/../x".parse().unwrap();
+    /// assert_eq!(base.resolve(&location).unwrap(), location);
+    /// ```
+    pub fn resolve(&self, reference: &Uri) -> Result<Uri, InvalidUri> {
+        resolve::resolve_uri(self, reference)
+    }
+
+    /// Parse `input` as a URI reference and resolve it against this `Uri`,
+    /// as a browser would.
+    ///
+    /// This is the same as `Uri::parse_with_base(self, input)`. In
+    /// particular, a reference with only a query, such as `?page=2`, keeps
+    /// the path of this `Uri` and replaces its query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let base: Uri = "http://example.com/a/b?x".parse().unwrap();
+    ///
+    /// assert_eq!(base.join("?y").unwrap(), "http://example.com/a/b?y");
+    /// assert_eq!(base.join("c").unwrap(), "http://example.com/a/c");
+    /// assert_eq!(base.join("../c").unwrap(), "http://example.com/c");
+    /// ```
+    pub fn join(&self, input: &str) -> Result<Uri, InvalidUri> {
+        resolve::resolve_str(self, input)
+    }
+
     /// Convert a `Uri` into `Parts`.
     ///
     /// # Note
@@ -537,6 +656,37 @@ impl Uri {
         self.authority_part().map(|a| a.host())
     }
 
+    /// Get an iterator over the dot-separated labels of the host, in order.
+    ///
+    /// This allows callers to implement their own suffix logic, such as
+    /// grouping by registrable domain. `None` is returned if the `Uri` has no
+    /// host, or if the host is an IP address, since those have no labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "http://a.b.example.com/".parse().unwrap();
+    /// let labels: Vec<&str> = uri.host_labels().unwrap().collect();
+    ///
+    /// assert_eq!(labels, ["a", "b", "example", "com"]);
+    ///
+    /// let uri: Uri = "http://127.0.0.1/".parse().unwrap();
+    /// assert!(uri.host_labels().is_none());
+    /// ```
+    pub fn host_labels<'a>(&'a self) -> Option<str::Split<'a, char>> {
+        let host = match self.host() {
+            Some(host) => host,
+            None => return None,
+        };
+
+        if host.starts_with('[') || host.parse::<Ipv4Addr>().is_ok() {
+            return None;
+        }
+
+        Some(host.split('.'))
+    }
+
     #[deprecated(since="0.1.14", note="use `port_part` or `port_u16` instead")]
     #[doc(hidden)]
     pub fn port(&self) -> Option<u16> {
@@ -606,6 +756,116 @@ impl Uri {
         self.port_part().and_then(|p| Some(p.as_u16()))
     }
 
+    /// Returns the origin of this `Uri`, formatted as the value of an
+    /// `Origin` header.
+    ///
+    /// As described by RFC 6454, Section 6.1, this is the lowercase scheme
+    /// and host, followed by the port if it is not the default port of the
+    /// scheme. The userinfo, path, and query are dropped. Schemes other than
+    /// `http`, `https`, `ws` and `wss` have an opaque origin, serialized as
+    /// `null`.
+    ///
+    /// `None` is returned if the URI has no scheme or no authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "https://user@Example.com:8443/path?q".parse().unwrap();
+    /// assert_eq!(uri.origin_header_value().unwrap(), "https://example.com:8443");
+    ///
+    /// let uri: Uri = "http://example.com:80/".parse().unwrap();
+    /// assert_eq!(uri.origin_header_value().unwrap(), "http://example.com");
+    ///
+    /// let uri: Uri = "/path".parse().unwrap();
+    /// assert!(uri.origin_header_value().is_none());
+    /// ```
+    pub fn origin_header_value(&self) -> Option<HeaderValue> {
+        let (scheme, authority) = match (self.scheme_part(), self.authority_part()) {
+            (Some(scheme), Some(authority)) => (scheme, authority),
+            _ => return None,
+        };
+
+        let default_port = match scheme.default_port() {
+            Some(port) => port,
+            None => return Some(HeaderValue::from_static("null")),
+        };
+
+        let mut origin = format!("{}://{}", scheme.as_str(), authority.host());
+
+        match authority.port_part() {
+            Some(ref port) if port.as_u16() != default_port => {
+                origin.push(':');
+                origin.push_str(port.as_str());
+            }
+            _ => {}
+        }
+
+        origin.make_ascii_lowercase();
+
+        // The scheme and authority only contain visible ASCII.
+        Some(HeaderValue::from_str(&origin).expect("origin is a valid header value"))
+    }
+
+    /// Returns the path of this `Uri` with its percent-encoded octets
+    /// decoded.
+    ///
+    /// The path is not split into segments, so its structure is preserved.
+    /// Decoded octets that do not form valid UTF-8 are replaced with
+    /// `U+FFFD`, and malformed escapes are left as is. The path is borrowed
+    /// when it contains no escapes.
+    ///
+    /// Note that an encoded slash, `%2F`, becomes a literal `/` in the
+    /// output, so `/a%2Fb` and `/a/b` decode to the same string. Use
+    /// `path_segments` instead when segment boundaries matter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "/static/hello%20world.txt".parse().unwrap();
+    ///
+    /// assert_eq!(uri.decoded_path(), "/static/hello world.txt");
+    /// ```
+    pub fn decoded_path<'a>(&'a self) -> Cow<'a, str> {
+        percent::decode(self.path())
+    }
+
+    /// Returns an iterator over the percent-decoded segments of the path.
+    ///
+    /// The path is split on `/` before decoding, so an encoded slash, `%2F`,
+    /// stays within its segment. The empty segment before the leading `/` is
+    /// skipped, while a trailing `/` yields a final empty segment; the path
+    /// `/` is a single empty segment. Segments are borrowed when they contain
+    /// no escapes.
+    ///
+    /// `None` is returned for URIs without a path, such as the authority-form
+    /// of a `CONNECT` request, and for the asterisk-form `*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "/users/jane%20doe/".parse().unwrap();
+    /// let segments: Vec<_> = uri.path_segments().unwrap().collect();
+    ///
+    /// assert_eq!(segments, ["users", "jane doe", ""]);
+    ///
+    /// let uri: Uri = "example.com:443".parse().unwrap();
+    /// assert!(uri.path_segments().is_none());
+    /// ```
+    pub fn path_segments<'a>(&'a self) -> Option<PathSegments<'a>> {
+        let path = self.path();
+
+        if !path.starts_with('/') {
+            return None;
+        }
+
+        Some(PathSegments {
+            segments: path[1..].split('/'),
+        })
+    }
+
     /// Get the query string of this `Uri`, starting after the `?`.
     ///
     /// The query component contains non-hierarchical data that, along with data
@@ -654,11 +914,152 @@ impl Uri {
         self.path_and_query.query()
     }
 
+    /// Returns an iterator over the `key=value` pairs of the query.
+    ///
+    /// The query is split into pairs on `&`, and each pair into a key and a
+    /// value on the first `=`. Both are percent-decoded, borrowing from the
+    /// URI when they contain no escapes. A key without `=`, such as `flag`
+    /// in `?flag`, has an empty value. Empty pairs are skipped, and a URI
+    /// without a query yields nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "/search?q=rust%20http&flag&page=2".parse().unwrap();
+    /// let pairs: Vec<_> = uri.query_pairs().collect();
+    ///
+    /// assert_eq!(pairs, [
+    ///     ("q".into(), "rust http".into()),
+    ///     ("flag".into(), "".into()),
+    ///     ("page".into(), "2".into()),
+    /// ]);
+    /// ```
+    pub fn query_pairs<'a>(&'a self) -> QueryPairs<'a> {
+        QueryPairs {
+            pairs: self.query().unwrap_or("").split('&'),
+        }
+    }
+
+    /// Returns true if displaying this `Uri` and parsing the result yields an
+    /// equal `Uri`.
+    ///
+    /// This holds for every `Uri` parsed from a string or built from parts,
+    /// except for the empty `Uri` built from no parts at all, which displays
+    /// as an empty string. It is intended as a debugging aid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let uri: Uri = "http://example.com?q".parse().unwrap();
+    /// assert_eq!(uri.to_string(), "http://example.com/?q");
+    /// assert!(uri.is_round_trip_stable());
+    /// ```
+    pub fn is_round_trip_stable(&self) -> bool {
+        match self.to_string().parse::<Uri>() {
+            Ok(uri) => uri == *self,
+            Err(_) => false,
+        }
+    }
+
+    /// Compares two URIs, treating a single trailing slash on the path as
+    /// insignificant.
+    ///
+    /// All other components must match as they would for `==`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let a: Uri = "/a".parse().unwrap();
+    /// let b: Uri = "/a/".parse().unwrap();
+    /// let c: Uri = "/a/b".parse().unwrap();
+    ///
+    /// assert!(a.eq_ignore_trailing_slash(&b));
+    /// assert!(!a.eq_ignore_trailing_slash(&c));
+    /// ```
+    pub fn eq_ignore_trailing_slash(&self, other: &Uri) -> bool {
+        fn trim(path: &str) -> &str {
+            if path.ends_with('/') {
+                &path[..path.len() - 1]
+            } else {
+                path
+            }
+        }
+
+        self.scheme_part() == other.scheme_part() &&
+            self.authority_part() == other.authority_part() &&
+            trim(self.path()) == trim(other.path()) &&
+            self.query() == other.query()
+    }
+
+    /// Compares two URIs, treating the query as an unordered collection of
+    /// parameters.
+    ///
+    /// Both queries are split into `key=value` pairs on `&` and each key and
+    /// value is percent-decoded. The URIs are equal if the resulting pairs are
+    /// the same, counting duplicates, regardless of their order. Empty pairs
+    /// are ignored, so a missing query equals an empty one. All other
+    /// components must match exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Uri;
+    /// let a: Uri = "/search?a=1&b=2".parse().unwrap();
+    /// let b: Uri = "/search?b=2&a=%31".parse().unwrap();
+    /// let c: Uri = "/search?a=1".parse().unwrap();
+    ///
+    /// assert!(a.query_eq_unordered(&b));
+    /// assert!(!a.query_eq_unordered(&c));
+    /// ```
+    pub fn query_eq_unordered(&self, other: &Uri) -> bool {
+        fn pairs<'a>(uri: &'a Uri) -> Vec<(Cow<'a, str>, Cow<'a, str>)> {
+            let mut pairs: Vec<_> = uri.query_pairs().collect();
+            pairs.sort();
+            pairs
+        }
+
+        self.scheme_part() == other.scheme_part() &&
+            self.authority_part() == other.authority_part() &&
+            self.path() == other.path() &&
+            pairs(self) == pairs(other)
+    }
+
     fn has_path(&self) -> bool {
         !self.path_and_query.data.is_empty() || !self.scheme.inner.is_none()
     }
 }
 
+impl<'a> Iterator for PathSegments<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Cow<'a, str>> {
+        self.segments.next().map(percent::decode)
+    }
+}
+
+impl<'a> Iterator for QueryPairs<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let pair = self.pairs.next()?;
+
+            if pair.is_empty() {
+                continue;
+            }
+
+            let mut kv = pair.splitn(2, '=');
+            let key = kv.next().unwrap_or("");
+            let value = kv.next().unwrap_or("");
+
+            return Some((percent::decode(key), percent::decode(value)));
+        }
+    }
+}
+
 impl TryFrom<Bytes> for Uri {
     type Error = InvalidUriBytes;
 
@@ -1125,6 +1526,9 @@ impl Error for InvalidUri {
             ErrorKind::TooLong => "uri too long",
             ErrorKind::Empty => "empty string",
             ErrorKind::SchemeTooLong => "scheme too long",
+            ErrorKind::HostTooLong => "host too long",
+            ErrorKind::LabelTooLong => "host label too long",
+            ErrorKind::PortOutOfRange => "port out of range",
         }
     }
 }
@@ -15,7 +15,7 @@ pub struct PathAndQuery {
     pub(super) query: u16,
 }
 
-const NONE: u16 = ::std::u16::MAX;
+pub(super) const NONE: u16 = ::std::u16::MAX;
 
 impl PathAndQuery {
     /// Attempt to convert a `PathAndQuery` from `Bytes`.
@@ -90,6 +90,19 @@ impl PathAndQuery {
         }
     }
 
+    pub(super) fn without_query(&self) -> Self {
+        if self.query == NONE {
+            return self.clone();
+        }
+
+        let path = Bytes::from(self.data.clone()).slice_to(self.query as usize);
+
+        PathAndQuery {
+            data: unsafe { ByteStr::from_utf8_unchecked(path) },
+            query: NONE,
+        }
+    }
+
     /// Returns the path component
     ///
     /// The path component is **case sensitive**.
@@ -0,0 +1,145 @@
+//! Percent-encoding helpers, as described by RFC 3986, Section 2.1.
+
+use std::borrow::Cow;
+
+/// Decodes the percent-encoded octets in `s`.
+///
+/// Malformed escapes are left as is. Decoded octets that do not form valid
+/// UTF-8 are replaced with `U+FFFD`.
+pub(super) fn decode<'a>(s: &'a str) -> Cow<'a, str> {
+    if !s.contains('%') {
+        return Cow::Borrowed(s);
+    }
+
+    let bytes = s.as_bytes();
+    let mut dst = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                dst.push(hi << 4 | lo);
+                i += 3;
+                continue;
+            }
+        }
+
+        dst.push(bytes[i]);
+        i += 1;
+    }
+
+    match String::from_utf8_lossy(&dst) {
+        Cow::Borrowed(s) => Cow::Owned(s.to_string()),
+        Cow::Owned(s) => Cow::Owned(s),
+    }
+}
+
+/// Percent-encodes `s` for use as a single path segment.
+///
+/// Every byte that is not a `pchar` of RFC 3986, Section 3.3, is encoded,
+/// including `/`, `?`, `#` and `%` itself. Sub-delimiters such as `;`, `=`
+/// and `&`, as well as `:` and `@`, are left intact.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::{self, PathAndQuery};
+/// let segment = uri::encode_path_segment("a b/c");
+/// assert_eq!(segment, "a%20b%2Fc");
+///
+/// let path: PathAndQuery = format!("/files/{}", segment).parse().unwrap();
+/// assert_eq!(path.path(), "/files/a%20b%2Fc");
+/// ```
+pub fn encode_path_segment(s: &str) -> String {
+    encode(s, is_pchar)
+}
+
+/// Percent-encodes `s` for use as the query component of a URI.
+///
+/// Every byte that is not allowed in a query by RFC 3986, Section 3.4, is
+/// encoded, including `#` and `%` itself. Reserved characters that are legal
+/// in a query, such as `&`, `=`, `/` and `?`, are left intact, so that `s`
+/// may contain several `key=value` pairs.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::{self, PathAndQuery};
+/// let query = uri::encode_query("q=rust http&page=2");
+/// assert_eq!(query, "q=rust%20http&page=2");
+///
+/// let path: PathAndQuery = format!("/search?{}", query).parse().unwrap();
+/// assert_eq!(path.query(), Some("q=rust%20http&page=2"));
+/// ```
+pub fn encode_query(s: &str) -> String {
+    encode(s, |b| is_pchar(b) || b == b'/' || b == b'?')
+}
+
+fn encode<F: Fn(u8) -> bool>(s: &str, allowed: F) -> String {
+    const HEX: &'static [u8; 16] = b"0123456789ABCDEF";
+
+    let mut dst = String::with_capacity(s.len());
+
+    for &b in s.as_bytes() {
+        if allowed(b) {
+            dst.push(b as char);
+        } else {
+            dst.push('%');
+            dst.push(HEX[(b >> 4) as usize] as char);
+            dst.push(HEX[(b & 0xf) as usize] as char);
+        }
+    }
+
+    dst
+}
+
+/// Returns true if `b` is a `pchar` of RFC 3986, other than a
+/// percent-encoded octet.
+fn is_pchar(b: u8) -> bool {
+    match b {
+        b'a'...b'z' | b'A'...b'Z' | b'0'...b'9' |
+        b'-' | b'.' | b'_' | b'~' |
+        b'!' | b'$' | b'&' | b'\'' | b'(' | b')' |
+        b'*' | b'+' | b',' | b';' | b'=' |
+        b':' | b'@' => true,
+        _ => false,
+    }
+}
+
+fn hex(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("plain"), "plain");
+        assert_eq!(decode("a%20b"), "a b");
+        assert_eq!(decode("%2Fa%2fb"), "/a/b");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz%4"), "%zz%4");
+        assert_eq!(decode("%C3%A9"), "\u{e9}");
+        assert_eq!(decode("%FF"), "\u{fffd}");
+    }
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("plain-Name_1.txt~"), "plain-Name_1.txt~");
+        assert_eq!(encode_path_segment("a b/c"), "a%20b%2Fc");
+        assert_eq!(encode_path_segment("100%?#"), "100%25%3F%23");
+        assert_eq!(encode_path_segment("k=v;a&b:c@d"), "k=v;a&b:c@d");
+        assert_eq!(encode_path_segment("caf\u{e9}"), "caf%C3%A9");
+        assert_eq!(decode(&encode_path_segment("a b/c%")), "a b/c%");
+    }
+
+    #[test]
+    fn test_encode_query() {
+        assert_eq!(encode_query("a=1&b=2"), "a=1&b=2");
+        assert_eq!(encode_query("q=a b&path=/x?y"), "q=a%20b&path=/x?y");
+        assert_eq!(encode_query("100%#frag"), "100%25%23frag");
+        assert_eq!(encode_query("\"<>\\^`{|}"), "%22%3C%3E%5C%5E%60%7B%7C%7D");
+    }
+}
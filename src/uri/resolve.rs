@@ -0,0 +1,226 @@
+//! Reference resolution as described by RFC 3986, Section 5.2.
+
+use super::{InvalidUri, Uri};
+use super::path::NONE;
+use super::scheme::Scheme2;
+
+/// The components of a URI reference.
+///
+/// Fragments are not represented since `Uri` does not retain them.
+struct Reference<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+}
+
+impl<'a> Reference<'a> {
+    /// Split a URI reference into its components, following the grammar of
+    /// RFC 3986, Appendix B.
+    fn parse(s: &'a str) -> Reference<'a> {
+        // Drop the fragment, it never takes part in resolution
+        let s = match s.find('#') {
+            Some(i) => &s[..i],
+            None => s,
+        };
+
+        let (s, query) = match s.find('?') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        let (scheme, s) = match s.find(|c| c == ':' || c == '/') {
+            Some(i) if s.as_bytes()[i] == b':' && is_scheme(&s[..i]) => {
+                (Some(&s[..i]), &s[i + 1..])
+            }
+            _ => (None, s),
+        };
+
+        let (authority, path) = if s.starts_with("//") {
+            let s = &s[2..];
+            match s.find('/') {
+                Some(i) => (Some(&s[..i]), &s[i..]),
+                None => (Some(s), ""),
+            }
+        } else {
+            (None, s)
+        };
+
+        Reference {
+            scheme: scheme,
+            authority: authority,
+            path: path,
+            query: query,
+        }
+    }
+}
+
+/// Resolve the reference `input` against `base`.
+pub(super) fn resolve_str(base: &Uri, input: &str) -> Result<Uri, InvalidUri> {
+    resolve(base, Reference::parse(input))
+}
+
+/// Resolve the `Uri` reference `reference` against `base`.
+///
+/// An absolute `reference` is returned unchanged.
+pub(super) fn resolve_uri(base: &Uri, reference: &Uri) -> Result<Uri, InvalidUri> {
+    match reference.scheme.inner {
+        Scheme2::None | Scheme2::Relative => {}
+        _ => return Ok(reference.clone()),
+    }
+
+    // `Uri::path` reports an empty path as `/`, but resolution must tell the
+    // two apart.
+    let path_and_query = &reference.path_and_query;
+    let path = match path_and_query.query {
+        NONE => &path_and_query.data[..],
+        i => &path_and_query.data[..i as usize],
+    };
+
+    resolve(base, Reference {
+        scheme: None,
+        authority: reference.authority_part().map(|a| a.as_str()),
+        path: path,
+        query: reference.query(),
+    })
+}
+
+fn resolve(base: &Uri, r: Reference) -> Result<Uri, InvalidUri> {
+    let base_authority = base.authority_part().map(|a| a.as_str());
+
+    let (scheme, authority, path, query) = if r.scheme.is_some() {
+        (r.scheme, r.authority, remove_dot_segments(r.path), r.query)
+    } else if r.authority.is_some() {
+        (base.scheme_str(), r.authority, remove_dot_segments(r.path), r.query)
+    } else if r.path.is_empty() {
+        let query = r.query.or(base.query());
+        (base.scheme_str(), base_authority, base.path().to_string(), query)
+    } else {
+        let path = if r.path.starts_with('/') {
+            remove_dot_segments(r.path)
+        } else {
+            remove_dot_segments(&merge(base, r.path))
+        };
+        (base.scheme_str(), base_authority, path, r.query)
+    };
+
+    let mut target = String::with_capacity(path.len() + 32);
+
+    // A relative (`//`) base scheme is represented by an empty string
+    if let Some(scheme) = scheme {
+        if !scheme.is_empty() {
+            target.push_str(scheme);
+            target.push(':');
+        }
+    }
+
+    if let Some(authority) = authority {
+        target.push_str("//");
+        target.push_str(authority);
+    }
+
+    target.push_str(&path);
+
+    if let Some(query) = query {
+        target.push('?');
+        target.push_str(query);
+    }
+
+    target.parse()
+}
+
+/// Merge a relative path with the path of `base`, as per RFC 3986,
+/// Section 5.2.3.
+fn merge(base: &Uri, path: &str) -> String {
+    let base_path = base.path();
+
+    if base.authority_part().is_some() && base_path.is_empty() {
+        return format!("/{}", path);
+    }
+
+    match base_path.rfind('/') {
+        Some(i) => format!("{}{}", &base_path[..i + 1], path),
+        None => path.to_string(),
+    }
+}
+
+/// Remove `.` and `..` segments from a path, as per RFC 3986, Section 5.2.4.
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+
+    while !input.is_empty() {
+        if input.starts_with("../") {
+            input = &input[3..];
+        } else if input.starts_with("./") || input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") {
+            input = &input[3..];
+            pop_segment(&mut output);
+        } else if input == "/.." {
+            input = "/";
+            pop_segment(&mut output);
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = if input.starts_with('/') { 1 } else { 0 };
+            let end = input[start..].find('/')
+                .map(|i| i + start)
+                .unwrap_or(input.len());
+
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+
+    output
+}
+
+fn pop_segment(output: &mut String) {
+    match output.rfind('/') {
+        Some(i) => output.truncate(i),
+        None => output.clear(),
+    }
+}
+
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+
+    match bytes.next() {
+        Some(b) if b.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+
+    bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_dot_segments() {
+        assert_eq!(remove_dot_segments("/a/b/c/./../../g"), "/a/g");
+        assert_eq!(remove_dot_segments("mid/content=5/../6"), "mid/6");
+        assert_eq!(remove_dot_segments("/a/.."), "/");
+        assert_eq!(remove_dot_segments("/../a"), "/a");
+        assert_eq!(remove_dot_segments(".."), "");
+    }
+
+    #[test]
+    fn test_reference_parse() {
+        let r = Reference::parse("http://example.com/a?b#c");
+        assert_eq!(r.scheme, Some("http"));
+        assert_eq!(r.authority, Some("example.com"));
+        assert_eq!(r.path, "/a");
+        assert_eq!(r.query, Some("b"));
+
+        let r = Reference::parse("../a:b");
+        assert_eq!(r.scheme, None);
+        assert_eq!(r.authority, None);
+        assert_eq!(r.path, "../a:b");
+        assert_eq!(r.query, None);
+    }
+}
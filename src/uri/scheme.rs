@@ -96,6 +96,46 @@ impl Scheme {
         }
     }
 
+    /// Returns the default port of the scheme, if it is a well-known one.
+    ///
+    /// This is the port to connect to when a URI does not specify one. The
+    /// list is hard-coded from the IANA defaults: 80 for `http` and `ws`,
+    /// and 443 for `https` and `wss`. `None` is returned for any other
+    /// scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// assert_eq!(Scheme::HTTPS.default_port(), Some(443));
+    ///
+    /// let scheme: Scheme = "ws".parse().unwrap();
+    /// assert_eq!(scheme.default_port(), Some(80));
+    ///
+    /// let scheme: Scheme = "ftp".parse().unwrap();
+    /// assert_eq!(scheme.default_port(), None);
+    /// ```
+    pub fn default_port(&self) -> Option<u16> {
+        use self::Scheme2::*;
+        use self::Protocol::*;
+
+        let other = match self.inner {
+            Standard(Http) => return Some(80),
+            Standard(Https) => return Some(443),
+            Other(ref v) => &v[..],
+            Relative | None => return Option::None,
+        };
+
+        // Other schemes keep their original case.
+        if other.eq_ignore_ascii_case("http") || other.eq_ignore_ascii_case("ws") {
+            Some(80)
+        } else if other.eq_ignore_ascii_case("https") || other.eq_ignore_ascii_case("wss") {
+            Some(443)
+        } else {
+            Option::None
+        }
+    }
+
     /// Converts this `Scheme` back to a sequence of bytes
     #[inline]
     pub fn into_bytes(self) -> Bytes {
@@ -19,12 +19,20 @@
 //! println!("{:?}", http2);
 //! ```
 
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
 /// Represents a version of the HTTP spec.
 #[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
 pub struct Version(Http);
 
+/// A possible error when converting a `Version` from a string.
+#[derive(Debug)]
+pub struct InvalidVersion {
+    _priv: (),
+}
+
 impl Version {
     /// `HTTP/0.9`
     pub const HTTP_09: Version = Version(Http::Http09);
@@ -37,6 +45,32 @@ impl Version {
 
     /// `HTTP/2.0`
     pub const HTTP_2: Version = Version(Http::H2);
+
+    /// `HTTP/3.0`
+    pub const HTTP_3: Version = Version(Http::H3);
+
+    /// Returns the string representation of the version, as used on the
+    /// HTTP/1 request and status lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::HTTP_11.as_str(), "HTTP/1.1");
+    /// assert_eq!(Version::default().as_str(), "HTTP/1.1");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        use self::Http::*;
+
+        match self.0 {
+            Http09 => "HTTP/0.9",
+            Http10 => "HTTP/1.0",
+            Http11 => "HTTP/1.1",
+            H2     => "HTTP/2.0",
+            H3     => "HTTP/3.0",
+        }
+    }
 }
 
 #[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
@@ -45,6 +79,7 @@ enum Http {
     Http10,
     Http11,
     H2,
+    H3,
 }
 
 impl Default for Version {
@@ -56,13 +91,106 @@ impl Default for Version {
 
 impl fmt::Debug for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use self::Http::*;
+        f.write_str(self.as_str())
+    }
+}
 
-        f.write_str(match self.0 {
-            Http09 => "HTTP/0.9",
-            Http10 => "HTTP/1.0",
-            Http11 => "HTTP/1.1",
-            H2     => "HTTP/2.0",
-        })
+impl FromStr for Version {
+    type Err = InvalidVersion;
+
+    /// Parses the protocol version token of an HTTP/1 request or status line.
+    ///
+    /// The token is matched exactly, so `http/1.1` is rejected. Both
+    /// `HTTP/2` and `HTTP/2.0` are accepted, as are `HTTP/3` and `HTTP/3.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!("HTTP/1.1".parse::<Version>().unwrap(), Version::HTTP_11);
+    /// assert_eq!("HTTP/2".parse::<Version>().unwrap(), Version::HTTP_2);
+    /// assert!("HTTP/1.2".parse::<Version>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Version, InvalidVersion> {
+        match s {
+            "HTTP/0.9" => Ok(Version::HTTP_09),
+            "HTTP/1.0" => Ok(Version::HTTP_10),
+            "HTTP/1.1" => Ok(Version::HTTP_11),
+            "HTTP/2" | "HTTP/2.0" => Ok(Version::HTTP_2),
+            "HTTP/3" | "HTTP/3.0" => Ok(Version::HTTP_3),
+            _ => Err(InvalidVersion { _priv: () }),
+        }
+    }
+}
+
+impl fmt::Display for InvalidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid HTTP version")
+    }
+}
+
+impl Error for InvalidVersion {
+    fn description(&self) -> &str {
+        "invalid HTTP version"
+    }
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(Version::default(), Version::HTTP_11);
+}
+
+#[test]
+fn test_as_str() {
+    assert_eq!(Version::HTTP_09.as_str(), "HTTP/0.9");
+    assert_eq!(Version::HTTP_10.as_str(), "HTTP/1.0");
+    assert_eq!(Version::HTTP_11.as_str(), "HTTP/1.1");
+    assert_eq!(Version::HTTP_2.as_str(), "HTTP/2.0");
+    assert_eq!(Version::HTTP_3.as_str(), "HTTP/3.0");
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(format!("{:?}", Version::HTTP_11), "HTTP/1.1");
+    assert_eq!(format!("{:?}", Version::HTTP_2), "HTTP/2.0");
+    assert_eq!(format!("{:?}", Version::HTTP_3), "HTTP/3.0");
+}
+
+#[test]
+fn test_ord() {
+    assert!(Version::HTTP_09 < Version::HTTP_10);
+    assert!(Version::HTTP_10 < Version::HTTP_11);
+    assert!(Version::HTTP_11 < Version::HTTP_2);
+    assert!(Version::HTTP_2 < Version::HTTP_3);
+    assert!(Version::HTTP_3 != Version::HTTP_2);
+}
+
+#[test]
+fn test_from_str() {
+    let valid = [
+        ("HTTP/0.9", Version::HTTP_09),
+        ("HTTP/1.0", Version::HTTP_10),
+        ("HTTP/1.1", Version::HTTP_11),
+        ("HTTP/2.0", Version::HTTP_2),
+        ("HTTP/2", Version::HTTP_2),
+        ("HTTP/3", Version::HTTP_3),
+        ("HTTP/3.0", Version::HTTP_3),
+    ];
+
+    for &(s, version) in &valid {
+        assert_eq!(s.parse::<Version>().unwrap(), version, "{:?}", s);
+    }
+
+    let invalid = [
+        "HTTP/1.2",
+        "SPDY/3",
+        "http/1.1",
+        "HTTP/1.1 ",
+        "HTTP/1",
+        "",
+    ];
+
+    for s in &invalid {
+        assert!(s.parse::<Version>().is_err(), "{:?}", s);
     }
 }
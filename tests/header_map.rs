@@ -327,3 +327,677 @@ fn value_htab() {
     HeaderValue::from_static("hello\tworld");
     HeaderValue::from_str("hello\tworld").unwrap();
 }
+
+#[test]
+fn eq_with_sensitivity() {
+    let mut a = HeaderMap::new();
+    a.insert("foo", "bar".parse().unwrap());
+    a.append("foo", "baz".parse().unwrap());
+
+    let mut b = a.clone();
+    assert!(a.eq_with_sensitivity(&b));
+
+    if let Entry::Occupied(mut e) = b.entry("foo").unwrap() {
+        e.iter_mut().nth(1).unwrap().set_sensitive(true);
+    }
+
+    assert_eq!(a, b);
+    assert!(!a.eq_with_sensitivity(&b));
+    assert!(!b.eq_with_sensitivity(&a));
+}
+
+#[test]
+fn try_append_bounded() {
+    let mut headers = HeaderMap::new();
+
+    assert!(!headers.try_append_bounded(VIA, "a".parse().unwrap(), 3).unwrap());
+    assert!(headers.try_append_bounded(VIA, "b".parse().unwrap(), 3).unwrap());
+    assert!(headers.try_append_bounded(VIA, "c".parse().unwrap(), 3).unwrap());
+    assert!(headers.try_append_bounded(VIA, "d".parse().unwrap(), 3).is_err());
+
+    let values: Vec<_> = headers.get_all(VIA).iter().collect();
+    assert_eq!(values, ["a", "b", "c"]);
+
+    // Other names are unaffected by the cap
+    assert!(!headers.try_append_bounded(HOST, "e".parse().unwrap(), 1).unwrap());
+    assert!(headers.try_append_bounded(HOST, "f".parse().unwrap(), 1).is_err());
+    assert!(headers.try_append_bounded(HOST, "f".parse().unwrap(), 0).is_err());
+}
+
+#[test]
+fn to_wire_bytes() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    headers.insert("X-Custom", "a".parse().unwrap());
+    headers.append("X-Custom", "b".parse().unwrap());
+
+    let expected: &[u8] = b"content-type: text/plain\r\n\
+                            x-custom: a\r\n\
+                            x-custom: b\r\n";
+
+    assert_eq!(headers.to_wire_bytes(), expected);
+    assert!(HeaderMap::new().to_wire_bytes().is_empty());
+}
+
+#[test]
+fn write_to() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    headers.insert("X-Custom", "a".parse().unwrap());
+    headers.append("X-Custom", "b".parse().unwrap());
+
+    let mut buf = Vec::new();
+    headers.write_to(&mut buf).unwrap();
+
+    let expected: &[u8] = b"content-type: text/plain\r\n\
+                            x-custom: a\r\n\
+                            x-custom: b\r\n";
+
+    assert_eq!(buf, expected);
+    assert_eq!(buf, headers.to_wire_bytes());
+}
+
+#[test]
+fn from_env_style() {
+    let lines = [
+        "# request defaults",
+        "Content-Type: text/plain",
+        "   ",
+        "  x-custom :a ",
+        "X-Custom: b",
+        "  # indented comment",
+        "Via: 1.1 proxy:8080",
+    ];
+
+    let headers = HeaderMap::from_env_style(lines.iter()).unwrap();
+
+    assert_eq!(headers.len(), 4);
+    assert_eq!(headers[CONTENT_TYPE], "text/plain");
+    assert_eq!(headers.get_all("x-custom").iter().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(headers[VIA], "1.1 proxy:8080");
+
+    assert!(HeaderMap::from_env_style(Vec::<String>::new()).unwrap().is_empty());
+    assert!(HeaderMap::from_env_style(&["no colon"]).is_err());
+    assert!(HeaderMap::from_env_style(&["bad name: value"]).is_err());
+    assert!(HeaderMap::from_env_style(&["name: \x7f"]).is_err());
+}
+
+#[test]
+fn from_headers() {
+    #[derive(Debug)]
+    struct Entity {
+        content_type: String,
+        content_length: Option<u64>,
+    }
+
+    impl FromHeaders for Entity {
+        fn from_headers(map: &HeaderMap) -> std::result::Result<Entity, HeaderError> {
+            let content_type = require(map, CONTENT_TYPE)?
+                .to_str()
+                .map_err(|_| HeaderError::invalid(CONTENT_TYPE))?
+                .to_string();
+
+            let content_length = match optional(map, CONTENT_LENGTH)? {
+                Some(value) => {
+                    let len = value.to_str().ok().and_then(|s| s.parse().ok());
+                    Some(len.ok_or_else(|| HeaderError::invalid(CONTENT_LENGTH))?)
+                }
+                None => None,
+            };
+
+            Ok(Entity {
+                content_type: content_type,
+                content_length: content_length,
+            })
+        }
+    }
+
+    let mut headers = HeaderMap::new();
+
+    let err = Entity::from_headers(&headers).unwrap_err();
+    assert!(err.is_missing());
+    assert_eq!(err.name(), CONTENT_TYPE);
+
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    let entity = Entity::from_headers(&headers).unwrap();
+    assert_eq!(entity.content_type, "text/plain");
+    assert_eq!(entity.content_length, None);
+
+    headers.insert(CONTENT_LENGTH, "42".parse().unwrap());
+    let entity = Entity::from_headers(&headers).unwrap();
+    assert_eq!(entity.content_length, Some(42));
+
+    headers.insert(CONTENT_LENGTH, "-1".parse().unwrap());
+    let err = Entity::from_headers(&headers).unwrap_err();
+    assert!(err.is_invalid());
+    assert_eq!(err.to_string(), "invalid header value: content-length");
+
+    headers.remove(CONTENT_LENGTH);
+    headers.append(CONTENT_TYPE, "text/html".parse().unwrap());
+    let err = Entity::from_headers(&headers).unwrap_err();
+    assert!(err.is_duplicate());
+    assert_eq!(err.name(), CONTENT_TYPE);
+}
+
+#[test]
+fn eq_ignoring() {
+    let mut a = HeaderMap::new();
+    a.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    a.insert(DATE, "Tue, 15 Nov 1994 08:12:31 GMT".parse().unwrap());
+
+    let mut b = a.clone();
+    b.insert(DATE, "Wed, 16 Nov 1994 08:12:31 GMT".parse().unwrap());
+
+    assert_ne!(a, b);
+    assert!(a.eq_ignoring(&b, &[DATE]));
+    assert!(!a.eq_ignoring(&b, &[SERVER]));
+
+    // Ignored headers may be missing from one side
+    b.remove(DATE);
+    assert!(a.eq_ignoring(&b, &[DATE]));
+    assert!(b.eq_ignoring(&a, &[DATE]));
+
+    // Other headers must still match exactly
+    b.append(CONTENT_TYPE, "text/html".parse().unwrap());
+    assert!(!a.eq_ignoring(&b, &[DATE]));
+
+    b.remove(CONTENT_TYPE);
+    b.insert(SERVER, "http".parse().unwrap());
+    assert!(!a.eq_ignoring(&b, &[DATE]));
+    assert!(a.eq_ignoring(&b, &[DATE, CONTENT_TYPE, SERVER]));
+}
+
+#[test]
+fn get_str() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+
+    assert_eq!(headers.get_str(CONTENT_TYPE), Some("text/plain"));
+    assert_eq!(headers.get_str("Content-Type"), Some("text/plain"));
+    assert_eq!(headers.get_str(&CONTENT_TYPE), Some("text/plain"));
+    assert_eq!(headers.get_str("x-opaque"), None);
+    assert_eq!(headers.get_str("x-absent"), None);
+}
+
+#[test]
+fn retain() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    headers.insert("x-multi", "1".parse().unwrap());
+    headers.append("x-multi", "2".parse().unwrap());
+    headers.append("x-multi", "3".parse().unwrap());
+    headers.insert(CONTENT_LENGTH, "0".parse().unwrap());
+
+    // Remove the middle value of a multi-valued key
+    headers.retain(|_, value| value != "2");
+    assert_eq!(headers.len(), 4);
+    assert_eq!(headers.get_all("x-multi").iter().collect::<Vec<_>>(), ["1", "3"]);
+    assert_eq!(headers.get_last("x-multi").unwrap(), "3");
+
+    // Remove a single-valued key, and mutate the survivors
+    headers.retain(|name, value| {
+        *value = format!("{}!", value.to_str().unwrap()).parse().unwrap();
+        name != CONTENT_LENGTH
+    });
+    assert!(!headers.contains_key(CONTENT_LENGTH));
+    assert_eq!(headers[CONTENT_TYPE], "text/plain!");
+
+    let order: Vec<_> = headers.iter().map(|(k, v)| (k.as_str(), v.to_str().unwrap())).collect();
+    assert_eq!(order, [("content-type", "text/plain!"), ("x-multi", "1!"), ("x-multi", "3!")]);
+
+    // Remove the first value of a multi-valued key
+    headers.retain(|_, value| value != "1!");
+    assert_eq!(headers["x-multi"], "3!");
+    assert_eq!(headers.get_all("x-multi").iter().count(), 1);
+
+    headers.retain(|_, _| false);
+    assert!(headers.is_empty());
+    assert_eq!(headers.keys_len(), 0);
+    assert!(headers.get(CONTENT_TYPE).is_none());
+
+    headers.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    assert_eq!(headers[CONTENT_TYPE], "text/html");
+}
+
+#[test]
+fn retain_many_keys_in_place() {
+    let mut headers = HeaderMap::new();
+    for i in 0..100 {
+        let name: HeaderName = format!("x-{}", i).parse().unwrap();
+        for j in 0..(i % 4) + 1 {
+            headers.append(name.clone(), j.to_string().parse().unwrap());
+        }
+    }
+
+    // Drop every third key entirely, and every value "1".
+    headers.retain(|name, value| {
+        let i: usize = name.as_str()[2..].parse().unwrap();
+        i % 3 != 0 && value != "1"
+    });
+
+    let mut expected = Vec::new();
+    for i in (0..100).filter(|i| i % 3 != 0) {
+        for j in (0..(i % 4) + 1).filter(|&j| j != 1) {
+            expected.push((format!("x-{}", i), j.to_string()));
+        }
+    }
+
+    let actual: Vec<_> = headers.iter()
+        .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap().to_string()))
+        .collect();
+    assert_eq!(actual, expected);
+    assert_eq!(headers.len(), expected.len());
+
+    for i in 0..100 {
+        let name = format!("x-{}", i);
+        assert_eq!(headers.contains_key(&name[..]), i % 3 != 0, "{}", name);
+        assert_eq!(headers.get_all(&name[..]).iter().count(), headers.get_all(&name[..]).len());
+    }
+}
+
+#[test]
+fn retain_many_values_of_one_key() {
+    let mut headers = HeaderMap::new();
+    for i in 0..10_000 {
+        headers.append(VIA, i.to_string().parse().unwrap());
+    }
+
+    headers.retain(|_, value| value.to_str().unwrap().parse::<usize>().unwrap() % 3 == 0);
+
+    let expected: Vec<_> = (0..10_000).filter(|i| i % 3 == 0).map(|i| i.to_string()).collect();
+    let actual: Vec<_> = headers.get_all(VIA).iter().map(|v| v.to_str().unwrap().to_string()).collect();
+    assert_eq!(actual, expected);
+    assert_eq!(headers.get_all(VIA).iter().len(), expected.len());
+    assert_eq!(headers.len(), expected.len());
+    assert_eq!(headers.get_last(VIA).unwrap(), "9999");
+}
+
+#[test]
+fn retain_leaves_map_consistent_if_predicate_panics() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mut headers = HeaderMap::new();
+    headers.insert(HOST, "example.com".parse().unwrap());
+    headers.append(VIA, "a".parse().unwrap());
+    headers.append(VIA, "b".parse().unwrap());
+    headers.append(VIA, "c".parse().unwrap());
+    headers.insert(ACCEPT, "*/*".parse().unwrap());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        headers.retain(|name, value| {
+            if value == "c" {
+                panic!("predicate panicked");
+            }
+            name != HOST && value != "b"
+        });
+    }));
+    assert!(result.is_err());
+
+    // Nothing was lost, other than the value that was already unlinked.
+    assert_eq!(headers.get_all(VIA).iter().collect::<Vec<_>>(), ["a", "c"]);
+    assert_eq!(headers[HOST], "example.com");
+    assert_eq!(headers[ACCEPT], "*/*");
+    assert_eq!(headers.len(), 4);
+
+    headers.append(VIA, "d".parse().unwrap());
+    assert_eq!(headers.get_all(VIA).iter().collect::<Vec<_>>(), ["a", "c", "d"]);
+}
+
+#[test]
+fn try_insert_and_try_append_at_max_size() {
+    let mut headers = HeaderMap::new();
+    let mut i = 0;
+
+    let err = loop {
+        let name = HeaderName::from_bytes(format!("x-{}", i).as_bytes()).unwrap();
+
+        match headers.try_append(name, HeaderValue::from_static("a")) {
+            Ok(prev) => assert!(!prev),
+            Err(err) => break err,
+        }
+
+        i += 1;
+    };
+
+    assert_eq!(i, 32_768);
+    assert_eq!(headers.len(), 32_768);
+    assert_eq!(err.to_string(), "max header map size reached");
+
+    let name = HeaderName::from_bytes(b"x-new").unwrap();
+    assert!(headers.try_insert(name, HeaderValue::from_static("a")).is_err());
+
+    // Existing names can still be updated.
+    let name = HeaderName::from_bytes(b"x-0").unwrap();
+    assert!(headers.try_append(&name, HeaderValue::from_static("b")).unwrap());
+    assert_eq!(
+        headers.try_insert(&name, HeaderValue::from_static("c")).unwrap().unwrap(),
+        "a");
+    assert_eq!(headers.len(), 32_768);
+}
+
+#[test]
+fn original_case() {
+    let mut headers = HeaderMap::new();
+    let custom = HeaderName::from_static("x-custom-header");
+
+    headers.insert_with_original_case(custom.clone(), "X-Custom-Header", "a".parse().unwrap());
+    headers.append(custom.clone(), "b".parse().unwrap());
+    headers.insert(HOST, "example.com".parse().unwrap());
+
+    assert_eq!(headers["x-custom-header"], "a");
+    assert_eq!(headers.get(&custom).unwrap(), "a");
+
+    let pairs: Vec<_> = headers.original_case_iter()
+        .map(|(name, value)| (name, value.to_str().unwrap()))
+        .collect();
+    assert_eq!(pairs, [
+        ("X-Custom-Header", "a"),
+        ("X-Custom-Header", "b"),
+        ("host", "example.com"),
+    ]);
+
+    // The casing goes away with the name.
+    headers.remove(&custom);
+    headers.insert(custom, "c".parse().unwrap());
+
+    let names: Vec<_> = headers.original_case_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["host", "x-custom-header"]);
+}
+
+#[test]
+#[should_panic]
+fn original_case_must_match_name() {
+    let mut headers = HeaderMap::new();
+    headers.insert_with_original_case(HOST, "Hots", "example.com".parse().unwrap());
+}
+
+#[test]
+fn case_preservation() {
+    let mut headers: HeaderMap = HeaderMap::with_case_preservation();
+
+    headers.insert("Content-Type", "text/plain".parse().unwrap());
+    headers.append("x-Trace", "a".parse().unwrap());
+    headers.append("X-TRACE", "b".parse().unwrap());
+    headers.insert(HOST, "example.com".parse().unwrap());
+
+    assert_eq!(headers[CONTENT_TYPE], "text/plain");
+    assert_eq!(headers.original_name(CONTENT_TYPE), Some("Content-Type"));
+    assert_eq!(headers.original_name("content-type"), Some("Content-Type"));
+    assert_eq!(headers.original_name("x-trace"), Some("X-TRACE"));
+    assert_eq!(headers.original_name(HOST), None);
+
+    headers.retain(|name, _| name != "x-trace");
+    assert_eq!(headers.original_name("x-trace"), None);
+    assert_eq!(headers.original_name(CONTENT_TYPE), Some("Content-Type"));
+
+    headers.clear();
+    headers.insert("Accept", "*/*".parse().unwrap());
+    assert_eq!(headers.original_name(ACCEPT), Some("Accept"));
+
+    // Without the mode, string keys don't record their casing.
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-Type", "text/plain".parse().unwrap());
+    assert_eq!(headers.original_name(CONTENT_TYPE), None);
+}
+
+#[test]
+fn original_case_of_runtime_names() {
+    let raw: Vec<(&[u8], &str)> = vec![
+        (b"X-Request-Id", "1"),
+        (b"set-cookie", "a=1"),
+        (b"Set-Cookie", "b=2"),
+        (b"HOST", "example.com"),
+    ];
+
+    let mut headers = HeaderMap::new();
+    for &(name, value) in &raw {
+        let key = HeaderName::from_bytes(name).unwrap();
+        headers.append_with_original_case(key, name, value.parse().unwrap());
+    }
+
+    assert_eq!(headers.original_name("x-request-id"), Some("X-Request-Id"));
+    assert_eq!(headers.original_name(SET_COOKIE), Some("Set-Cookie"));
+    assert_eq!(headers.original_name(HOST), Some("HOST"));
+    assert_eq!(headers.get_all(SET_COOKIE).iter().count(), 2);
+
+    let names: Vec<_> = headers.original_case_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["X-Request-Id", "Set-Cookie", "Set-Cookie", "HOST"]);
+
+    // A lowercase casing replaces the recorded one.
+    headers.insert_with_original_case(HOST, &b"host"[..], "example.org".parse().unwrap());
+    assert_eq!(headers.original_name(HOST), None);
+
+    headers.remove(SET_COOKIE);
+    assert_eq!(headers.original_name(SET_COOKIE), None);
+}
+
+#[test]
+fn merge_smart() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    headers.insert(SET_COOKIE, "a=1".parse().unwrap());
+    headers.insert(VIA, "1.1 a".parse().unwrap());
+
+    let mut defaults = HeaderMap::new();
+    defaults.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+    defaults.insert(SET_COOKIE, "b=2".parse().unwrap());
+    defaults.append(SET_COOKIE, "c=3".parse().unwrap());
+    defaults.insert(HOST, "example.com".parse().unwrap());
+
+    headers.merge_smart(defaults);
+
+    assert_eq!(headers.get_all(CONTENT_TYPE).iter().collect::<Vec<_>>(), ["application/json"]);
+    assert_eq!(headers.get_all(SET_COOKIE).iter().collect::<Vec<_>>(), ["a=1", "b=2", "c=3"]);
+    assert_eq!(headers.get_all(VIA).iter().collect::<Vec<_>>(), ["1.1 a"]);
+    assert_eq!(headers[HOST], "example.com");
+    assert_eq!(headers.len(), 6);
+}
+
+#[test]
+fn growth_strategies() {
+    for &growth in &[GrowthStrategy::Double, GrowthStrategy::OneAndAHalf] {
+        let mut headers: HeaderMap = HeaderMap::with_capacity_and_growth(3, growth);
+
+        for i in 0..1000 {
+            let name = HeaderName::from_bytes(format!("x-{}", i).as_bytes()).unwrap();
+            headers.insert(name.clone(), i.to_string().parse().unwrap());
+            headers.append(name, "extra".parse().unwrap());
+        }
+
+        assert_eq!(headers.len(), 2000);
+        assert_eq!(headers.keys_len(), 1000);
+
+        for i in 0..1000 {
+            let name = format!("x-{}", i);
+            let values: Vec<_> = headers.get_all(&name).iter().collect();
+            assert_eq!(values, [&i.to_string()[..], "extra"], "{:?}", growth);
+        }
+
+        for i in (0..1000).filter(|i| i % 2 == 0) {
+            assert!(headers.remove(&format!("x-{}", i)).is_some());
+        }
+
+        assert_eq!(headers.keys_len(), 500);
+        assert!(headers.contains_key("x-1"));
+        assert!(!headers.contains_key("x-0"));
+    }
+}
+
+#[test]
+fn get_first_and_last() {
+    let mut headers = HeaderMap::new();
+    assert!(headers.get_first("x-value").is_none());
+    assert!(headers.get_last("x-value").is_none());
+
+    headers.append("x-value", "1".parse().unwrap());
+    headers.append("x-value", "2".parse().unwrap());
+    headers.append("x-value", "3".parse().unwrap());
+
+    assert_eq!(headers.get_first("x-value").unwrap(), "1");
+    assert_eq!(headers.get_last("x-value").unwrap(), "3");
+
+    headers.insert("x-other", "only".parse().unwrap());
+    assert_eq!(headers.get_first("x-other").unwrap(), "only");
+    assert_eq!(headers.get_last("x-other").unwrap(), "only");
+}
+
+#[test]
+fn compact_shares_equal_values() {
+    // Long enough not to be stored inline.
+    let value = "no-cache, no-store, must-revalidate, max-age=0";
+
+    let mut headers = HeaderMap::new();
+    headers.append(CACHE_CONTROL, HeaderValue::from_str(value).unwrap());
+    headers.insert(PRAGMA, HeaderValue::from_str(value).unwrap());
+
+    let mut sensitive = HeaderValue::from_str(value).unwrap();
+    sensitive.set_sensitive(true);
+    headers.append(CACHE_CONTROL, sensitive);
+
+    fn ptrs(headers: &HeaderMap) -> Vec<*const u8> {
+        headers.values().map(|v| v.as_bytes().as_ptr()).collect()
+    }
+
+    let before = ptrs(&headers);
+    assert!(before[0] != before[1]);
+    assert!(before[0] != before[2]);
+
+    headers.compact();
+
+    let after = ptrs(&headers);
+    assert_eq!(after[0], before[0]);
+    assert!(after.iter().all(|&p| p == after[0]));
+
+    assert!(headers.values().all(|v| v == value));
+    let sensitivity: Vec<_> = headers.get_all(CACHE_CONTROL).iter().map(|v| v.is_sensitive()).collect();
+    assert_eq!(sensitivity, [false, true]);
+}
+
+#[test]
+fn ensure_present_lists_missing_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert(HOST, "example.com".parse().unwrap());
+    headers.insert(ACCEPT, "*/*".parse().unwrap());
+
+    assert!(headers.ensure_present(&[]).is_ok());
+    assert!(headers.ensure_present(&[HOST, ACCEPT]).is_ok());
+
+    let err = headers.ensure_present(&[USER_AGENT, HOST, CONTENT_LENGTH]).unwrap_err();
+    assert_eq!(err.names(), &[USER_AGENT, CONTENT_LENGTH]);
+    assert_eq!(err.to_string(), "missing required headers: user-agent, content-length");
+
+    let err: Error = err.into();
+    assert_eq!(err.to_string(), "missing required headers");
+}
+
+#[test]
+fn entry_with_str_keys() {
+    let mut headers: HeaderMap<u32> = HeaderMap::default();
+    headers.insert("x-present", 1);
+
+    assert_eq!(*headers.entry("x-present").unwrap().or_insert(10), 1);
+    assert_eq!(*headers.entry("x-absent").unwrap().or_insert(10), 10);
+
+    assert_eq!(*headers.entry("X-Present").unwrap().or_insert_with(|| unreachable!()), 1);
+    assert_eq!(*headers.entry("x-lazy").unwrap().or_insert_with(|| 20), 20);
+
+    headers.entry("x-present").unwrap().and_modify(|n| *n += 1).or_insert(0);
+    headers.entry("x-new").unwrap().and_modify(|n| *n += 1).or_insert(0);
+    assert_eq!(headers["x-present"], 2);
+    assert_eq!(headers["x-new"], 0);
+
+    assert!(headers.entry("bad name").is_err());
+    assert!(headers.entry("").is_err());
+    assert_eq!(headers.len(), 4);
+}
+
+#[test]
+fn append_all_preserves_order() {
+    let mut headers = HeaderMap::new();
+    headers.insert(HOST, "example.com".parse().unwrap());
+    headers.append_all(SET_COOKIE, vec![
+        "a=1".parse().unwrap(),
+        "b=2".parse().unwrap(),
+        "c=3".parse().unwrap(),
+    ]);
+
+    let cookies: Vec<_> = headers.get_all(SET_COOKIE).iter().collect();
+    assert_eq!(cookies, ["a=1", "b=2", "c=3"]);
+
+    headers.append_all(SET_COOKIE, Some("d=4".parse().unwrap()));
+    let cookies: Vec<_> = headers.get_all(SET_COOKIE).iter().collect();
+    assert_eq!(cookies, ["a=1", "b=2", "c=3", "d=4"]);
+
+    assert_eq!(headers.keys_len(), 2);
+    assert_eq!(headers.len(), 5);
+}
+
+#[test]
+fn append_all_empty_is_noop() {
+    let mut headers = HeaderMap::new();
+    headers.append_all(SET_COOKIE, vec![]);
+    assert!(headers.is_empty());
+    assert!(!headers.contains_key(SET_COOKIE));
+
+    headers.insert(SET_COOKIE, "a=1".parse().unwrap());
+    headers.append_all(SET_COOKIE, vec![]);
+    assert_eq!(headers.len(), 1);
+}
+
+#[test]
+fn get_all_len() {
+    let mut headers = HeaderMap::new();
+    headers.insert(HOST, "example.com".parse().unwrap());
+    headers.append(VIA, "a".parse().unwrap());
+    headers.append(VIA, "b".parse().unwrap());
+    headers.append(VIA, "c".parse().unwrap());
+
+    assert_eq!(headers.get_all(HOST).len(), 1);
+    assert_eq!(headers.get_all(VIA).len(), 3);
+    assert_eq!(headers.get_all(ACCEPT).len(), 0);
+    assert!(headers.get_all(ACCEPT).is_empty());
+
+    let mut iter = headers.get_all(VIA).iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.len(), 2);
+    iter.next_back();
+    assert_eq!(iter.len(), 1);
+    iter.next();
+    assert_eq!(iter.len(), 0);
+    assert!(iter.next().is_none());
+    assert_eq!(iter.len(), 0);
+    assert_eq!(headers.get_all(ACCEPT).iter().len(), 0);
+
+    // The stored count follows removals.
+    if let Entry::Occupied(mut e) = headers.entry(VIA).unwrap() {
+        e.insert("d".parse().unwrap());
+    }
+    assert_eq!(headers.get_all(VIA).len(), 1);
+    headers.append_all(VIA, vec!["e".parse().unwrap(), "f".parse().unwrap()]);
+    assert_eq!(headers.get_all(VIA).len(), 3);
+    headers.remove(HOST);
+    assert_eq!(headers.get_all(VIA).len(), 3);
+}
+
+#[test]
+fn get_many_returns_first_values_in_order() {
+    let mut headers = HeaderMap::new();
+    headers.insert(ORIGIN, "https://example.com".parse().unwrap());
+    headers.append(ACCESS_CONTROL_REQUEST_HEADERS, "x-a".parse().unwrap());
+    headers.append(ACCESS_CONTROL_REQUEST_HEADERS, "x-b".parse().unwrap());
+
+    let values = headers.get_many(&[
+        ACCESS_CONTROL_REQUEST_METHOD,
+        ORIGIN,
+        ACCESS_CONTROL_REQUEST_HEADERS,
+    ]);
+
+    assert_eq!(values.len(), 3);
+    assert!(values[0].is_none());
+    assert_eq!(values[1].unwrap(), "https://example.com");
+    assert_eq!(values[2].unwrap(), "x-a");
+
+    assert!(headers.get_many(&[]).is_empty());
+}
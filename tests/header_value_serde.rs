@@ -0,0 +1,65 @@
+#![cfg(feature = "serde")]
+
+extern crate bincode;
+extern crate http;
+extern crate serde_json;
+
+use http::header::HeaderValue;
+
+#[test]
+fn round_trips_ascii_value_as_string() {
+    let value = HeaderValue::from_static("text/html; charset=utf-8");
+
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#""text/html; charset=utf-8""#);
+
+    let decoded: HeaderValue = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn round_trips_opaque_value_as_bytes() {
+    let value = HeaderValue::from_bytes(b"caf\xe9").unwrap();
+
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, "[99,97,102,233]");
+
+    let decoded: HeaderValue = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn does_not_serialize_sensitivity() {
+    let mut value = HeaderValue::from_static("secret");
+    value.set_sensitive(true);
+
+    let json = serde_json::to_string(&value).unwrap();
+    let decoded: HeaderValue = serde_json::from_str(&json).unwrap();
+    assert!(!decoded.is_sensitive());
+}
+
+#[test]
+fn rejects_invalid_values() {
+    assert!(serde_json::from_str::<HeaderValue>(r#""a\nb""#).is_err());
+    assert!(serde_json::from_str::<HeaderValue>("[97,10,98]").is_err());
+    assert!(serde_json::from_str::<HeaderValue>("[256]").is_err());
+    assert!(serde_json::from_str::<HeaderValue>("42").is_err());
+}
+
+#[test]
+fn round_trips_through_a_non_self_describing_format() {
+    let values = [
+        HeaderValue::from_static("text/html; charset=utf-8"),
+        HeaderValue::from_bytes(b"caf\xe9").unwrap(),
+        HeaderValue::from_static(""),
+    ];
+
+    for value in &values {
+        let encoded = bincode::serialize(value).unwrap();
+        let decoded: HeaderValue = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(&decoded, value);
+    }
+
+    let encoded = bincode::serialize("a\nb").unwrap();
+    assert!(bincode::deserialize::<HeaderValue>(&encoded).is_err());
+}
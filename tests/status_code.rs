@@ -65,3 +65,103 @@ test_round_trip!(
     560, 561, 562, 563, 564, 565, 566, 567, 568, 569, 570, 571, 572, 573, 574, 575, 576, 577, 578, 579,
     580, 581, 582, 583, 584, 585, 586, 587, 588, 589, 590, 591, 592, 593, 594, 595, 596, 597, 598, 599,
     );
+
+#[test]
+fn must_have_empty_body() {
+    assert!(StatusCode::CONTINUE.must_have_empty_body());
+    assert!(StatusCode::SWITCHING_PROTOCOLS.must_have_empty_body());
+    assert!(StatusCode::NO_CONTENT.must_have_empty_body());
+    assert!(StatusCode::NOT_MODIFIED.must_have_empty_body());
+
+    assert!(!StatusCode::OK.must_have_empty_body());
+    assert!(!StatusCode::RESET_CONTENT.must_have_empty_body());
+    assert!(!StatusCode::NOT_FOUND.must_have_empty_body());
+}
+
+#[test]
+fn display_with_reason() {
+    assert_eq!(StatusCode::OK.display_with_reason().to_string(), "200 OK");
+    assert_eq!(StatusCode::NOT_FOUND.display_with_reason().to_string(), "404 Not Found");
+
+    let unknown = StatusCode::from_u16(299).unwrap();
+    assert_eq!(unknown.display_with_reason().to_string(), "299");
+    assert_eq!(format!("[{}]", unknown.display_with_reason()), "[299]");
+}
+
+#[test]
+fn class_digit() {
+    assert_eq!(StatusCode::CONTINUE.class_digit(), 1);
+    assert_eq!(StatusCode::NO_CONTENT.class_digit(), 2);
+    assert_eq!(StatusCode::NOT_MODIFIED.class_digit(), 3);
+    assert_eq!(StatusCode::NOT_FOUND.class_digit(), 4);
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE.class_digit(), 5);
+    assert_eq!(StatusCode::from_u16(599).unwrap().class_digit(), 5);
+}
+
+#[test]
+fn is_error_and_err() {
+    assert!(!StatusCode::OK.is_error());
+    assert!(StatusCode::NOT_FOUND.is_error());
+    assert!(StatusCode::INTERNAL_SERVER_ERROR.is_error());
+    assert!(!StatusCode::MOVED_PERMANENTLY.is_error());
+
+    assert_eq!(StatusCode::OK.err(), Ok(StatusCode::OK));
+    assert_eq!(StatusCode::NOT_FOUND.err(), Err(StatusCode::NOT_FOUND));
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR.err(), Err(StatusCode::INTERNAL_SERVER_ERROR));
+
+    fn check(status: StatusCode) -> ::std::result::Result<u16, StatusCode> {
+        let status = status.err()?;
+        Ok(status.as_u16())
+    }
+
+    assert_eq!(check(StatusCode::OK), Ok(200));
+    assert_eq!(check(StatusCode::NOT_FOUND), Err(StatusCode::NOT_FOUND));
+}
+
+#[test]
+fn class_predicates_at_boundaries() {
+    use http::status::StatusClass::*;
+
+    let cases = [
+        (100, Informational),
+        (199, Informational),
+        (200, Success),
+        (299, Success),
+        (300, Redirection),
+        (399, Redirection),
+        (400, ClientError),
+        (499, ClientError),
+        (500, ServerError),
+        (599, ServerError),
+    ];
+
+    for &(code, class) in cases.iter() {
+        let status = StatusCode::from_u16(code).unwrap();
+
+        assert_eq!(status.class(), class, "{}", code);
+        assert_eq!(status.is_informational(), class == Informational, "{}", code);
+        assert_eq!(status.is_success(), class == Success, "{}", code);
+        assert_eq!(status.is_redirection(), class == Redirection, "{}", code);
+        assert_eq!(status.is_client_error(), class == ClientError, "{}", code);
+        assert_eq!(status.is_server_error(), class == ServerError, "{}", code);
+    }
+}
+
+#[test]
+fn converts_into_error() {
+    fn handler(authorized: bool) -> Result<StatusCode> {
+        if !authorized {
+            return Err(StatusCode::UNAUTHORIZED.into());
+        }
+        Ok(StatusCode::OK)
+    }
+
+    assert_eq!(handler(true).unwrap(), StatusCode::OK);
+
+    let err = handler(false).unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(err.to_string(), "401 Unauthorized");
+
+    let err: Error = StatusCode::from_u16(99).unwrap_err().into();
+    assert_eq!(err.status(), None);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "typed-headers")]
+
+extern crate http;
+
+use http::HeaderMap;
+use http::header::*;
+
+#[test]
+fn content_length_round_trips() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(headers.typed_get::<ContentLength>(), None);
+
+    headers.typed_insert(ContentLength(1234));
+    assert_eq!(headers[CONTENT_LENGTH], "1234");
+    assert_eq!(headers.typed_get::<ContentLength>(), Some(ContentLength(1234)));
+
+    headers.typed_insert(ContentLength(0));
+    assert_eq!(headers.get_all(CONTENT_LENGTH).iter().count(), 1);
+    assert_eq!(headers.typed_get::<ContentLength>(), Some(ContentLength(0)));
+}
+
+#[test]
+fn malformed_content_length_is_none() {
+    let mut headers = HeaderMap::new();
+
+    for bad in &["-1", "12abc", "", "1 2"] {
+        headers.insert(CONTENT_LENGTH, bad.parse().unwrap());
+        assert_eq!(headers.typed_get::<ContentLength>(), None, "{:?}", bad);
+    }
+
+    let err = ContentLength::decode(&HeaderValue::from_static("x")).unwrap_err();
+    assert!(err.is_invalid());
+    assert_eq!(err.name(), CONTENT_LENGTH);
+}
+
+#[test]
+fn content_type_round_trips() {
+    let mut headers = HeaderMap::new();
+
+    headers.typed_insert(ContentType::new("text/html; charset=utf-8").unwrap());
+    assert_eq!(headers[CONTENT_TYPE], "text/html; charset=utf-8");
+
+    let content_type = headers.typed_get::<ContentType>().unwrap();
+    assert_eq!(content_type.as_str(), "text/html; charset=utf-8");
+
+    for bad in &["text", "/html", "text/", "a/b/c"] {
+        headers.insert(CONTENT_TYPE, bad.parse().unwrap());
+        assert_eq!(headers.typed_get::<ContentType>(), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn content_type_rejects_invalid_values() {
+    for bad in &["a\nb/c", "text/html\r\n", "text/h\u{e9}", "text"] {
+        let err = ContentType::new(bad).unwrap_err();
+        assert!(err.is_invalid(), "{:?}", bad);
+        assert_eq!(err.name(), CONTENT_TYPE);
+    }
+}